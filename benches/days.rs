@@ -0,0 +1,70 @@
+//! Benchmarks the parsing and both parts of every registered day, each in its own group.
+//!
+//! Runs on the real inputs in `input/2024` by default, skipping days without one. Set
+//! `AOC_BENCH_INPUT=example` to run on the embedded examples instead.
+//!
+//! To compare two branches, save a baseline on the first and compare against it on the second,
+//! criterion flagging the regressions:
+//!
+//! ```sh
+//! git switch main && cargo bench --bench days -- --save-baseline main
+//! git switch my-branch && cargo bench --bench days -- --baseline main
+//! ```
+//!
+//! A filter such as `cargo bench --bench days -- day6/` only runs the matching days.
+
+use std::{env, fs, hint::black_box};
+
+use advent_2024::{
+    examples::EXAMPLES,
+    solution::{Solver, REGISTRY},
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn example_input(day: u32, part: u32) -> Option<&'static str> {
+    EXAMPLES
+        .iter()
+        .find(|e| e.day == day && e.part == part)
+        .map(|e| e.input)
+}
+
+/// The inputs of the parse, part 1 and part 2 benchmarks
+fn inputs(solver: &Solver, use_examples: bool) -> [Option<String>; 3] {
+    if use_examples {
+        let part1 = example_input(solver.day, 1).map(str::to_string);
+        let part2 = example_input(solver.day, 2).map(str::to_string);
+        [part1.clone().or_else(|| part2.clone()), part1, part2]
+    } else {
+        let input = fs::read_to_string(format!("input/2024/day{}.txt", solver.day)).ok();
+        [input.clone(), input.clone(), input]
+    }
+}
+
+fn bench_days(c: &mut Criterion) {
+    let use_examples = env::var("AOC_BENCH_INPUT").is_ok_and(|v| v == "example");
+    for solver in REGISTRY {
+        let [parse_input, part1_input, part2_input] = inputs(solver, use_examples);
+        let Some(parse_input) = parse_input else {
+            eprintln!("day{}: no input, skipped", solver.day);
+            continue;
+        };
+        let mut group = c.benchmark_group(format!("day{}", solver.day));
+        group.sample_size(10);
+        group.bench_function("parse", |b| {
+            b.iter(|| solver.parse(black_box(&parse_input)));
+        });
+        for (part, input) in [(1, part1_input), (2, part2_input)] {
+            let Some(input) = input else {
+                continue;
+            };
+            let parsed = solver.parse(&input);
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| solver.solve(part, black_box(&parsed)));
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
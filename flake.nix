@@ -48,19 +48,6 @@
           cargo-edit
           cargo-watch
           rust-analyzer
-          # cargo AoC
-          (rustPlatform.buildRustPackage
-            {
-              pname = "cargo-aoc";
-              version = "0.3.7-c260ff4";
-              src = fetchFromGitHub {
-                owner = "gobanos";
-                repo = "cargo-aoc";
-                rev = "c260ff45120bbfcbed2983b9d220919a1d98efd1";
-                hash = "sha256-uYkq6TTIHB3beA4fjxnsSClRflZo9ol+UIzE9lq4pfg=";
-              };
-              cargoHash = "sha256-GziKzPgXw7I+S5rSxfydfpUD7fY38SFP6Q1IeYnfmag=";
-            })
         ];
 
         env = {
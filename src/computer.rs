@@ -0,0 +1,453 @@
+//! The 3-bit chronospatial computer of day 17
+
+use std::{error::Error, fmt};
+
+mod assembler;
+
+pub use assembler::{assemble, AssembleError, AssembleErrorKind};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+impl Registers {
+    #[must_use]
+    pub const fn get(&self, register: Register) -> u64 {
+        match register {
+            Register::A => self.a,
+            Register::B => self.b,
+            Register::C => self.c,
+        }
+    }
+
+    const fn set(&mut self, register: Register, value: u64) {
+        match register {
+            Register::A => self.a = value,
+            Register::B => self.b = value,
+            Register::C => self.c = value,
+        }
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A={} B={} C={}", self.a, self.b, self.c)
+    }
+}
+
+/// An operand standing either for itself or for the value of a register
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Combo {
+    Literal(u8),
+    Register(Register),
+}
+
+impl Combo {
+    #[must_use]
+    pub const fn value(self, registers: &Registers) -> u64 {
+        match self {
+            Self::Literal(n) => n as u64,
+            Self::Register(register) => registers.get(register),
+        }
+    }
+
+    const fn encode(self) -> u8 {
+        match self {
+            Self::Literal(n) => n,
+            Self::Register(Register::A) => 4,
+            Self::Register(Register::B) => 5,
+            Self::Register(Register::C) => 6,
+        }
+    }
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(n) => write!(f, "{n}"),
+            Self::Register(register) => write!(f, "{register:?}"),
+        }
+    }
+}
+
+/// A decoded instruction, with the operand it reads
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Adv(Combo),
+    Bxl(u8),
+    Bst(Combo),
+    Jnz(u8),
+    /// The operand is read but ignored
+    Bxc(u8),
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+}
+
+impl Instruction {
+    /// Decodes the instruction at `ip`, `None` once `ip` is past the end of the program
+    ///
+    /// # Errors
+    /// if the opcode is unknown, or the operand is missing or invalid for the opcode
+    pub fn fetch(program: &[u8], ip: usize) -> Result<Option<Self>, ComputerError> {
+        let Some(&opcode) = program.get(ip) else {
+            return Ok(None);
+        };
+        let &operand = program
+            .get(ip + 1)
+            .ok_or(ComputerError::MissingOperand { ip })?;
+        let invalid = ComputerError::InvalidOperand { ip, operand };
+        let literal = || {
+            if operand < 8 {
+                Ok(operand)
+            } else {
+                Err(invalid)
+            }
+        };
+        let combo = || match operand {
+            0..=3 => Ok(Combo::Literal(operand)),
+            4 => Ok(Combo::Register(Register::A)),
+            5 => Ok(Combo::Register(Register::B)),
+            6 => Ok(Combo::Register(Register::C)),
+            _ => Err(invalid),
+        };
+        let instruction = match opcode {
+            0 => Self::Adv(combo()?),
+            1 => Self::Bxl(literal()?),
+            2 => Self::Bst(combo()?),
+            3 => Self::Jnz(literal()?),
+            4 => Self::Bxc(literal()?),
+            5 => Self::Out(combo()?),
+            6 => Self::Bdv(combo()?),
+            7 => Self::Cdv(combo()?),
+            _ => return Err(ComputerError::InvalidOpcode { ip, opcode }),
+        };
+        Ok(Some(instruction))
+    }
+
+    /// The opcode and operand, as they appear in a program
+    #[must_use]
+    pub const fn encode(self) -> [u8; 2] {
+        match self {
+            Self::Adv(combo) => [0, combo.encode()],
+            Self::Bxl(literal) => [1, literal],
+            Self::Bst(combo) => [2, combo.encode()],
+            Self::Jnz(literal) => [3, literal],
+            Self::Bxc(literal) => [4, literal],
+            Self::Out(combo) => [5, combo.encode()],
+            Self::Bdv(combo) => [6, combo.encode()],
+            Self::Cdv(combo) => [7, combo.encode()],
+        }
+    }
+
+    #[must_use]
+    pub const fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Adv(_) => "adv",
+            Self::Bxl(_) => "bxl",
+            Self::Bst(_) => "bst",
+            Self::Jnz(_) => "jnz",
+            Self::Bxc(_) => "bxc",
+            Self::Out(_) => "out",
+            Self::Bdv(_) => "bdv",
+            Self::Cdv(_) => "cdv",
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.mnemonic();
+        match self {
+            Self::Adv(combo)
+            | Self::Bst(combo)
+            | Self::Out(combo)
+            | Self::Bdv(combo)
+            | Self::Cdv(combo) => {
+                write!(f, "{mnemonic} {combo}")
+            }
+            Self::Bxl(literal) | Self::Jnz(literal) | Self::Bxc(literal) => {
+                write!(f, "{mnemonic} {literal}")
+            }
+        }
+    }
+}
+
+/// One listing line per instruction, prefixed with its address
+///
+/// # Errors
+/// if an instruction cannot be decoded
+pub fn disassemble(program: &[u8]) -> Result<String, ComputerError> {
+    let mut lines = Vec::new();
+    let mut ip = 0;
+    while let Some(instruction) = Instruction::fetch(program, ip)? {
+        lines.push(format!("{ip:>3}: {instruction}\n"));
+        ip += 2;
+    }
+    Ok(lines.concat())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ComputerError {
+    InvalidOpcode {
+        ip: usize,
+        opcode: u8,
+    },
+    /// Not a 3-bit number, or the reserved combo operand 7
+    InvalidOperand {
+        ip: usize,
+        operand: u8,
+    },
+    /// The last opcode of the program has no operand
+    MissingOperand {
+        ip: usize,
+    },
+    /// The program was still running after that many instructions
+    BudgetExhausted {
+        budget: usize,
+    },
+}
+
+impl fmt::Display for ComputerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOpcode { ip, opcode } => write!(f, "invalid opcode {opcode} at {ip}"),
+            Self::InvalidOperand { ip, operand } => write!(f, "invalid operand {operand} at {ip}"),
+            Self::MissingOperand { ip } => write!(f, "missing operand at {ip}"),
+            Self::BudgetExhausted { budget } => {
+                write!(f, "still running after {budget} instructions")
+            }
+        }
+    }
+}
+
+impl Error for ComputerError {}
+
+/// What executing one instruction did
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Write(Register, u64),
+    Jump(usize),
+    /// A `jnz` with `A` at zero
+    NoJump,
+    Output(u8),
+    /// The instruction pointer is past the end of the program, nothing was executed
+    Halted,
+}
+
+/// An executed instruction and the registers right after it
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub ip: usize,
+    pub instruction: Instruction,
+    pub event: Event,
+    pub registers: Registers,
+}
+
+#[derive(Clone, Debug)]
+pub struct Computer<'a> {
+    program: &'a [u8],
+    registers: Registers,
+    ip: usize,
+}
+
+impl<'a> Computer<'a> {
+    #[must_use]
+    pub const fn new(program: &'a [u8], registers: Registers) -> Self {
+        Self {
+            program,
+            registers,
+            ip: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    #[must_use]
+    pub const fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Executes the instruction at the instruction pointer
+    ///
+    /// # Errors
+    /// if the instruction cannot be decoded
+    pub fn step(&mut self) -> Result<Event, ComputerError> {
+        let Some(instruction) = Instruction::fetch(self.program, self.ip)? else {
+            return Ok(Event::Halted);
+        };
+        let registers = &self.registers;
+        // the shift amount can be any register value, which would overflow a plain `>>`
+        let divide = |combo: Combo| {
+            u32::try_from(combo.value(registers))
+                .ok()
+                .and_then(|shift| registers.a.checked_shr(shift))
+                .unwrap_or(0)
+        };
+        let event = match instruction {
+            Instruction::Adv(combo) => Event::Write(Register::A, divide(combo)),
+            Instruction::Bxl(literal) => {
+                Event::Write(Register::B, registers.b ^ u64::from(literal))
+            }
+            Instruction::Bst(combo) => Event::Write(Register::B, combo.value(registers) % 8),
+            Instruction::Jnz(_) if registers.a == 0 => Event::NoJump,
+            Instruction::Jnz(literal) => Event::Jump(literal.into()),
+            Instruction::Bxc(_) => Event::Write(Register::B, registers.b ^ registers.c),
+            #[allow(clippy::cast_possible_truncation, reason = "the value is below 8")]
+            Instruction::Out(combo) => Event::Output((combo.value(registers) % 8) as u8),
+            Instruction::Bdv(combo) => Event::Write(Register::B, divide(combo)),
+            Instruction::Cdv(combo) => Event::Write(Register::C, divide(combo)),
+        };
+        match event {
+            Event::Jump(target) => self.ip = target,
+            Event::Write(register, value) => {
+                self.registers.set(register, value);
+                self.ip += 2;
+            }
+            _ => self.ip += 2,
+        }
+        Ok(event)
+    }
+
+    /// Runs until the program halts, returning its output
+    ///
+    /// # Errors
+    /// if an instruction cannot be decoded, or the program runs more than `budget` instructions
+    pub fn run(&mut self, budget: usize) -> Result<Vec<u8>, ComputerError> {
+        let mut output = Vec::new();
+        for _ in 0..=budget {
+            match self.step()? {
+                Event::Halted => return Ok(output),
+                Event::Output(n) => output.push(n),
+                _ => {}
+            }
+        }
+        Err(ComputerError::BudgetExhausted { budget })
+    }
+
+    /// Runs until the program halts, recording every executed instruction
+    ///
+    /// # Errors
+    /// see [`Computer::run`]
+    pub fn trace(&mut self, budget: usize) -> Result<Vec<TraceEntry>, ComputerError> {
+        let mut entries = Vec::new();
+        for _ in 0..=budget {
+            let ip = self.ip;
+            let Some(instruction) = Instruction::fetch(self.program, ip)? else {
+                return Ok(entries);
+            };
+            let event = self.step()?;
+            entries.push(TraceEntry {
+                ip,
+                instruction,
+                event,
+                registers: self.registers,
+            });
+        }
+        Err(ComputerError::BudgetExhausted { budget })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let registers = Registers { a: 42, b: 42, c: 9 };
+        let mut computer = Computer::new(&[2, 6, 0, 7], registers);
+        assert_eq!(computer.step(), Ok(Event::Write(Register::B, 1)));
+        assert_eq!(computer.ip(), 2);
+        assert_eq!(
+            computer.step(),
+            Err(ComputerError::InvalidOperand { ip: 2, operand: 7 })
+        );
+        let mut computer = Computer::new(&[3, 4, 8, 0], registers);
+        assert_eq!(computer.step(), Ok(Event::Jump(4)));
+        assert_eq!(computer.step(), Ok(Event::Halted));
+        let mut computer = Computer::new(&[8, 0], registers);
+        assert_eq!(
+            computer.step(),
+            Err(ComputerError::InvalidOpcode { ip: 0, opcode: 8 })
+        );
+    }
+
+    #[test]
+    fn test_large_registers() {
+        let registers = Registers {
+            a: 1 << 40,
+            b: 35,
+            c: 0,
+        };
+        let mut computer = Computer::new(&[0, 5, 5, 4], registers);
+        assert_eq!(computer.run(10), Ok(vec![0]));
+        assert_eq!(computer.registers().a, 1 << 5);
+        // shifting by more than the register width clears it
+        let mut computer = Computer::new(
+            &[7, 5],
+            Registers {
+                b: 1 << 35,
+                ..registers
+            },
+        );
+        assert_eq!(computer.run(10), Ok(vec![]));
+        assert_eq!(computer.registers().c, 0);
+    }
+
+    #[test]
+    fn test_run_budget() {
+        let mut computer = Computer::new(&[3, 0], Registers { a: 1, b: 0, c: 0 });
+        assert_eq!(
+            computer.run(100),
+            Err(ComputerError::BudgetExhausted { budget: 100 })
+        );
+        let mut computer = Computer::new(&[0, 1, 5, 4, 3, 0], Registers { a: 6, b: 0, c: 0 });
+        assert_eq!(computer.run(100), Ok(vec![3, 1, 0]));
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut computer = Computer::new(&[0, 1, 5, 4, 3, 0], Registers { a: 2, b: 0, c: 0 });
+        let trace = computer.trace(100).unwrap();
+        let steps: Vec<_> = trace
+            .iter()
+            .map(|e| (e.ip, e.event, e.registers.a))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (0, Event::Write(Register::A, 1), 1),
+                (2, Event::Output(1), 1),
+                (4, Event::Jump(0), 1),
+                (0, Event::Write(Register::A, 0), 0),
+                (2, Event::Output(0), 0),
+                (4, Event::NoJump, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(
+            disassemble(&[2, 4, 1, 7, 7, 5, 4, 1, 0, 3, 5, 5, 3, 0]).unwrap(),
+            "  0: bst A\n  2: bxl 7\n  4: cdv B\n  6: bxc 1\n  8: adv 3\n 10: out B\n 12: jnz 0\n"
+        );
+        assert_eq!(
+            disassemble(&[0, 1, 5]),
+            Err(ComputerError::MissingOperand { ip: 2 })
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use super::{Combo, Instruction, Register};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssembleErrorKind {
+    UnknownMnemonic(String),
+    MissingOperand,
+    UnexpectedOperand(String),
+    /// Not a number from 0 to 7
+    InvalidLiteral(String),
+    /// Neither a number from 0 to 3 nor a register
+    InvalidCombo(String),
+    InvalidLabel(String),
+    DuplicateLabel(String),
+    UnknownLabel(String),
+    /// A label does not fit in the 3-bit operand of `jnz`
+    JumpOutOfRange {
+        label: String,
+        address: usize,
+    },
+    /// A numeric label, as in disassembly listings, is not the address of its instruction
+    AddressMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// An error and the line it was found on, starting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    pub line: usize,
+    pub kind: AssembleErrorKind,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            AssembleErrorKind::UnknownMnemonic(m) => write!(f, "unknown mnemonic {m:?}"),
+            AssembleErrorKind::MissingOperand => write!(f, "missing operand"),
+            AssembleErrorKind::UnexpectedOperand(op) => write!(f, "unexpected operand {op:?}"),
+            AssembleErrorKind::InvalidLiteral(op) => {
+                write!(f, "{op:?} should be a literal operand, from 0 to 7")
+            }
+            AssembleErrorKind::InvalidCombo(op) => {
+                write!(
+                    f,
+                    "{op:?} should be a combo operand, from 0 to 3 or A, B, C"
+                )
+            }
+            AssembleErrorKind::InvalidLabel(label) => write!(f, "invalid label {label:?}"),
+            AssembleErrorKind::DuplicateLabel(label) => write!(f, "duplicate label {label:?}"),
+            AssembleErrorKind::UnknownLabel(label) => write!(f, "unknown label {label:?}"),
+            AssembleErrorKind::JumpOutOfRange { label, address } => {
+                write!(f, "label {label:?} at {address} is out of reach of jnz")
+            }
+            AssembleErrorKind::AddressMismatch { expected, found } => {
+                write!(f, "address {found} should be {expected}")
+            }
+        }
+    }
+}
+
+impl Error for AssembleError {}
+
+/// A line with an instruction, before labels are resolved
+struct Line<'a> {
+    number: usize,
+    mnemonic: &'a str,
+    operand: Option<&'a str>,
+}
+
+/// Assembles one instruction per line, such as `bst A`, `bxl 7` or `jnz loop`, into a program
+///
+/// Comments start with `;`. Lines can start with `label:`, naming the address of the next
+/// instruction for `jnz`, or with the `4:` addresses of [`disassemble`](super::disassemble)
+/// listings, which are checked.
+///
+/// # Errors
+/// on the first line that is not a valid instruction
+pub fn assemble(source: &str) -> Result<Vec<u8>, AssembleError> {
+    let mut labels = HashMap::new();
+    let mut lines = Vec::new();
+    for (number, line) in (1..).zip(source.lines()) {
+        let error = |kind| AssembleError { line: number, kind };
+        let mut rest = line.split(';').next().unwrap_or_default().trim();
+        let address = 2 * lines.len();
+        while let Some((label, after)) = rest.split_once(':') {
+            let label = label.trim();
+            if let Ok(found) = label.parse() {
+                if found != address {
+                    return Err(error(AssembleErrorKind::AddressMismatch {
+                        expected: address,
+                        found,
+                    }));
+                }
+            } else if !is_identifier(label) {
+                return Err(error(AssembleErrorKind::InvalidLabel(label.to_string())));
+            } else if labels.insert(label, address).is_some() {
+                return Err(error(AssembleErrorKind::DuplicateLabel(label.to_string())));
+            }
+            rest = after.trim();
+        }
+        let mut words = rest.split_whitespace();
+        let Some(mnemonic) = words.next() else {
+            continue;
+        };
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(error(AssembleErrorKind::UnexpectedOperand(
+                extra.to_string(),
+            )));
+        }
+        lines.push(Line {
+            number,
+            mnemonic,
+            operand,
+        });
+    }
+
+    let mut program = Vec::with_capacity(2 * lines.len());
+    for line in lines {
+        let instruction = resolve(&line, &labels).map_err(|kind| AssembleError {
+            line: line.number,
+            kind,
+        })?;
+        program.extend(instruction.encode());
+    }
+    Ok(program)
+}
+
+fn is_identifier(label: &str) -> bool {
+    label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn resolve(line: &Line, labels: &HashMap<&str, usize>) -> Result<Instruction, AssembleErrorKind> {
+    let operand = || line.operand.ok_or(AssembleErrorKind::MissingOperand);
+    let literal = || {
+        let operand = operand()?;
+        operand
+            .parse()
+            .ok()
+            .filter(|&n| n < 8)
+            .ok_or_else(|| AssembleErrorKind::InvalidLiteral(operand.to_string()))
+    };
+    let combo = || {
+        let operand = operand()?;
+        match operand {
+            "A" => Ok(Combo::Register(Register::A)),
+            "B" => Ok(Combo::Register(Register::B)),
+            "C" => Ok(Combo::Register(Register::C)),
+            _ => operand
+                .parse()
+                .ok()
+                .filter(|&n| n < 4)
+                .map(Combo::Literal)
+                .ok_or_else(|| AssembleErrorKind::InvalidCombo(operand.to_string())),
+        }
+    };
+    let target = || {
+        let operand = operand()?;
+        if !is_identifier(operand) {
+            return literal();
+        }
+        let &address = labels
+            .get(operand)
+            .ok_or_else(|| AssembleErrorKind::UnknownLabel(operand.to_string()))?;
+        u8::try_from(address)
+            .ok()
+            .filter(|&n| n < 8)
+            .ok_or_else(|| AssembleErrorKind::JumpOutOfRange {
+                label: operand.to_string(),
+                address,
+            })
+    };
+    Ok(match line.mnemonic {
+        "adv" => Instruction::Adv(combo()?),
+        "bxl" => Instruction::Bxl(literal()?),
+        "bst" => Instruction::Bst(combo()?),
+        "jnz" => Instruction::Jnz(target()?),
+        // the operand is ignored, and can be left out
+        "bxc" if line.operand.is_none() => Instruction::Bxc(0),
+        "bxc" => Instruction::Bxc(literal()?),
+        "out" => Instruction::Out(combo()?),
+        "bdv" => Instruction::Bdv(combo()?),
+        "cdv" => Instruction::Cdv(combo()?),
+        mnemonic => return Err(AssembleErrorKind::UnknownMnemonic(mnemonic.to_string())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::disassemble;
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            ; prints the octal digits of A, lowest first
+            start:
+            loop: out A  ; only the lowest 3 bits
+                  adv 3
+                  bxc
+                  jnz loop
+        ";
+        assert_eq!(assemble(source).unwrap(), [5, 4, 0, 3, 4, 0, 3, 0]);
+    }
+
+    #[test]
+    fn test_disassembly_round_trip() {
+        let program = [2, 4, 1, 7, 7, 5, 4, 1, 0, 3, 5, 5, 3, 0];
+        assert_eq!(assemble(&disassemble(&program).unwrap()).unwrap(), program);
+    }
+
+    #[test]
+    fn test_invalid_operands() {
+        let kind = |source| assemble(source).unwrap_err().kind;
+        assert_eq!(
+            kind("bxl A"),
+            AssembleErrorKind::InvalidLiteral("A".to_string())
+        );
+        assert_eq!(
+            kind("out 4"),
+            AssembleErrorKind::InvalidCombo("4".to_string())
+        );
+        assert_eq!(
+            kind("jnz 8"),
+            AssembleErrorKind::InvalidLiteral("8".to_string())
+        );
+        assert_eq!(kind("adv"), AssembleErrorKind::MissingOperand);
+        assert_eq!(
+            kind("jnz end"),
+            AssembleErrorKind::UnknownLabel("end".to_string())
+        );
+        assert_eq!(
+            assemble("adv 3\n\n2: add 1").unwrap_err(),
+            AssembleError {
+                line: 3,
+                kind: AssembleErrorKind::UnknownMnemonic("add".to_string())
+            }
+        );
+        assert_eq!(
+            kind("adv 3\n4: out A"),
+            AssembleErrorKind::AddressMismatch {
+                expected: 2,
+                found: 4
+            }
+        );
+        let far = "out A\n".repeat(4) + "end: jnz end";
+        assert_eq!(
+            kind(&far),
+            AssembleErrorKind::JumpOutOfRange {
+                label: "end".to_string(),
+                address: 8
+            }
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
 
-#[aoc_generator(day1)]
 fn gen(input: &str) -> (Vec<i32>, Vec<i32>) {
     input
         .lines()
@@ -15,7 +17,6 @@ fn gen(input: &str) -> (Vec<i32>, Vec<i32>) {
         .unzip()
 }
 
-#[aoc(day1, part1)]
 #[allow(clippy::stable_sort_primitive)] // stable sort is faster by 20%
 fn p1((v1, v2): &(Vec<i32>, Vec<i32>)) -> u32 {
     let mut v1 = v1.clone();
@@ -28,7 +29,6 @@ fn p1((v1, v2): &(Vec<i32>, Vec<i32>)) -> u32 {
         .sum()
 }
 
-#[aoc(day1, part2)]
 fn p2((v1, v2): &(Vec<i32>, Vec<i32>)) -> i32 {
     let mut count = HashMap::new();
     for n2 in v2 {
@@ -37,33 +37,47 @@ fn p2((v1, v2): &(Vec<i32>, Vec<i32>)) -> i32 {
     v1.iter().map(|n1| *n1 * count.get(n1).unwrap_or(&0)).sum()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Day1;
 
-    #[test]
-    fn test_p1() {
-        let input = "3   4
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+    type Part1 = u32;
+    type Part2 = i32;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(gen(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(p2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "3   4
 4   3
 2   5
 1   3
 3   9
 3   3
 ";
-        let result = p1(&gen(input));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_p1() {
+        let result = p1(&gen(EXAMPLE));
         assert_eq!(result, 11);
     }
 
     #[test]
     fn test_p2() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3
-";
-        let result = p2(&gen(input));
+        let result = p2(&gen(EXAMPLE));
         assert_eq!(result, 31);
     }
 }
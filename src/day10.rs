@@ -1,10 +1,10 @@
-use std::collections::HashSet;
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    search::{self, SearchResult},
+    solution::Solution,
+};
 
-use aoc_runner_derive::{aoc, aoc_generator};
-
-use crate::matrix::Matrix;
-
-#[aoc_generator(day10)]
 fn parse(input: &str) -> Matrix<u32> {
     let width = input.lines().next().unwrap().len();
     let height = input.lines().count();
@@ -18,63 +18,78 @@ fn parse(input: &str) -> Matrix<u32> {
     Matrix::new(items, width, height)
 }
 
-fn dfs(topography: &Matrix<u32>, (x, y): (isize, isize), found_tops: &mut HashSet<(isize, isize)>) {
-    let cur = topography.get(x, y).unwrap();
-    if *cur == 9 {
-        found_tops.insert((x, y));
-    }
-    let neighbors = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
-    neighbors
-        .into_iter()
-        .filter(|(nx, ny)| topography.get(*nx, *ny).is_some_and(|n| *n == 1 + cur))
-        .for_each(|pos| {
-            dfs(topography, pos, found_tops);
-        });
+fn trails(topography: &Matrix<u32>, start: (isize, isize)) -> SearchResult<(isize, isize), usize> {
+    search::bfs(
+        start,
+        |&(x, y)| {
+            let cur = *topography.get(x, y).unwrap();
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(move |(nx, ny)| topography.get(*nx, *ny).is_some_and(|n| *n == 1 + cur))
+        },
+        |_| false,
+    )
 }
 
-#[aoc(day10, part1)]
-fn part1(topography: &Matrix<u32>) -> usize {
+fn tops(
+    topography: &Matrix<u32>,
+    trails: &SearchResult<(isize, isize), usize>,
+) -> Vec<(isize, isize)> {
+    trails
+        .distances()
+        .keys()
+        .filter(|(x, y)| topography.get(*x, *y) == Some(&9))
+        .copied()
+        .collect()
+}
+
+fn trailheads(topography: &Matrix<u32>) -> impl Iterator<Item = (isize, isize)> + '_ {
     topography
         .iter_pos()
         .filter(|(_, n)| **n == 0)
         .map(|(pos, _)| (pos.0.try_into().unwrap(), pos.1.try_into().unwrap()))
-        .map(|pos| {
-            let mut found_tops = HashSet::new();
-            dfs(topography, pos, &mut found_tops);
-            found_tops.len()
-        })
-        .sum()
 }
 
-fn dfs2(topography: &Matrix<u32>, (x, y): (isize, isize)) -> usize {
-    let cur = topography.get(x, y).unwrap();
-    if *cur == 9 {
-        return 1;
-    }
-    let neighbors = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
-    neighbors
-        .into_iter()
-        .filter(|(nx, ny)| topography.get(*nx, *ny).is_some_and(|n| *n == 1 + cur))
-        .map(|pos| dfs2(topography, pos))
+fn part1(topography: &Matrix<u32>) -> usize {
+    trailheads(topography)
+        .map(|pos| tops(topography, &trails(topography, pos)).len())
         .sum()
 }
 
-#[aoc(day10, part2)]
 fn part2(topography: &Matrix<u32>) -> usize {
-    topography
-        .iter_pos()
-        .filter(|(_, n)| **n == 0)
-        .map(|(pos, _)| (pos.0.try_into().unwrap(), pos.1.try_into().unwrap()))
-        .map(|pos| dfs2(topography, pos))
+    // every step climbs by one, so every trail to a top is a shortest path to it
+    trailheads(topography)
+        .map(|pos| {
+            let trails = trails(topography, pos);
+            tops(topography, &trails)
+                .iter()
+                .map(|top| trails.count_paths_to(top))
+                .sum::<usize>()
+        })
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
+pub struct Day10;
 
-    const EXAMPLE1: &str = "89010123
+impl Solution for Day10 {
+    type Input = Matrix<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE1: &str = "89010123
 78121874
 87430965
 96549874
@@ -83,7 +98,8 @@ mod tests {
 01329801
 10456732
 ";
-    const EXAMPLE2: &str = "...0...
+
+pub const EXAMPLE2: &str = "...0...
 ...1...
 ...2...
 6543456
@@ -92,7 +108,7 @@ mod tests {
 9.....9
 ";
 
-    const EXAMPLE3: &str = "..90..9
+pub const EXAMPLE3: &str = "..90..9
 ...1.98
 ...2..7
 6543456
@@ -101,7 +117,7 @@ mod tests {
 987....
 ";
 
-    const EXAMPLE4: &str = "10..9..
+pub const EXAMPLE4: &str = "10..9..
 2...8..
 3...7..
 4567654
@@ -110,6 +126,11 @@ mod tests {
 .....01
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
     #[test_case(EXAMPLE1 => 36; "normal example")]
     #[test_case(EXAMPLE2 => 2; "simple example")]
     #[test_case(EXAMPLE3 => 4; "unreachable top")]
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
 
-#[aoc_generator(day11)]
 fn parse(input: &str) -> HashMap<u64, usize> {
     let mut map = HashMap::new();
     input
@@ -40,23 +42,42 @@ fn blink_process(stones: &mut HashMap<u64, usize>, max_iter: usize) -> usize {
     stones.values().sum()
 }
 
-#[aoc(day11, part1)]
 fn part1(stones: &HashMap<u64, usize>) -> usize {
     let mut stones = stones.to_owned();
     blink_process(&mut stones, 25)
 }
 
-#[aoc(day11, part2)]
 fn part2(stones: &HashMap<u64, usize>) -> usize {
     let mut stones = stones.to_owned();
     blink_process(&mut stones, 75)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = HashMap<u64, usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "125 17";
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use test_case::test_case;
-    const EXAMPLE: &str = "125 17";
 
     #[test_case(EXAMPLE, 6 => 22; "normal example")]
     #[test_case(EXAMPLE, 25 => 55312; "normal example bigger")]
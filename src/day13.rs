@@ -1,26 +1,30 @@
-use crate::pos::Pos;
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    pos::Pos,
+    solution::Solution,
+    utils::linalg::{self, SystemSolution},
+};
 use std::sync::LazyLock;
 
-use aoc_runner_derive::{aoc, aoc_generator};
 use regex::Regex;
 
 static RE_DIFF: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"X\+(\d+), Y\+(\d+)").unwrap());
 static RE_TARGET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"X=(\d+), Y=(\d+)").unwrap());
 
 #[derive(Clone, Copy)]
-struct ClawMachine {
-    a: Pos<f64>,
-    b: Pos<f64>,
-    target: Pos<f64>,
+pub struct ClawMachine {
+    a: Pos<i64>,
+    b: Pos<i64>,
+    target: Pos<i64>,
 }
 
-#[aoc_generator(day13)]
 fn parse(input: &str) -> Vec<ClawMachine> {
-    fn parse_diff(line: &str) -> Pos<f64> {
+    fn parse_diff(line: &str) -> Pos<i64> {
         let [x, y] = RE_DIFF.captures(line).unwrap().extract().1;
         Pos::new(x.parse().unwrap(), y.parse().unwrap())
     }
-    fn parse_target(line: &str) -> Pos<f64> {
+    fn parse_target(line: &str) -> Pos<i64> {
         let [x, y] = RE_TARGET.captures(line).unwrap().extract().1;
         Pos::new(x.parse().unwrap(), y.parse().unwrap())
     }
@@ -36,33 +40,62 @@ fn parse(input: &str) -> Vec<ClawMachine> {
         .collect()
 }
 
-// can't be bothered to add num-traits dependency
-#[allow(clippy::cast_precision_loss)]
-#[allow(clippy::cast_sign_loss)]
-#[allow(clippy::cast_possible_truncation)]
+const COST_A: i128 = 3;
+const COST_B: i128 = 1;
+
+/// The fewest tokens needed to win the prize, if it can be won
 fn search(machine: &ClawMachine) -> Option<u64> {
-    let det = machine.a.x * machine.b.y - machine.a.y * machine.b.x;
-    let sol = (
-        machine.b.y * machine.target.x - machine.b.x * machine.target.y / det,
-        -machine.a.y * machine.target.x + machine.a.x * machine.target.y / det,
-    );
-
-    if (sol.0.round() - sol.0).abs() < f64::EPSILON && (sol.1.round() - sol.1).abs() < f64::EPSILON
-    {
-        Some(sol.0 as u64 * 3 + sol.1 as u64)
+    let (a, b, t) = (widen(machine.a), widen(machine.b), widen(machine.target));
+    let coefficients = Matrix::new([a.x, b.x, a.y, b.y], 2, 2);
+    let (presses_a, presses_b) = match linalg::solve(&coefficients, &[t.x, t.y]) {
+        SystemSolution::None => return None,
+        // the presses have to be whole and non-negative
+        SystemSolution::Unique(presses) => (presses[0].to_integer()?, presses[1].to_integer()?),
+        SystemSolution::Infinite { .. } => cheapest_collinear(a, b, t)?,
+    };
+    if presses_a < 0 || presses_b < 0 {
+        return None;
+    }
+    cost(presses_a, presses_b).try_into().ok()
+}
+
+fn widen(pos: Pos<i64>) -> Pos<i128> {
+    Pos::new(pos.x.into(), pos.y.into())
+}
+
+const fn cost(presses_a: i128, presses_b: i128) -> i128 {
+    COST_A * presses_a + COST_B * presses_b
+}
+
+/// When both buttons move along the same line as the target, the cost is linear along the
+/// solutions, so the cheapest one presses one of the buttons as much as possible
+fn cheapest_collinear(a: Pos<i128>, b: Pos<i128>, t: Pos<i128>) -> Option<(i128, i128)> {
+    // everything is proportional, so one non-degenerate axis is enough
+    let (step_a, step_b, target) = if a.x != 0 || b.x != 0 {
+        (a.x, b.x, t.x)
     } else {
-        None
+        (a.y, b.y, t.y)
+    };
+    match (step_a, step_b) {
+        (0, 0) => Some((0, 0)),
+        (0, _) => (target % step_b == 0).then(|| (0, target / step_b)),
+        (_, 0) => (target % step_a == 0).then(|| (target / step_a, 0)),
+        _ => {
+            let mut solutions = linalg::non_negative_solutions(step_a, step_b, target);
+            [solutions.next(), solutions.next_back()]
+                .into_iter()
+                .flatten()
+                .min_by_key(|&(presses_a, presses_b)| cost(presses_a, presses_b))
+        }
     }
 }
 
-#[aoc(day13, part1)]
 fn part1(machines: &[ClawMachine]) -> u64 {
     machines.iter().filter_map(search).sum()
 }
 
-#[aoc(day13, part2)]
 fn part2(machines: &[ClawMachine]) -> u64 {
-    let added = 10_000_000_000_000.0;
+    let added = 10_000_000_000_000;
     machines
         .iter()
         .map(|&m| ClawMachine {
@@ -73,12 +106,27 @@ fn part2(machines: &[ClawMachine]) -> u64 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<ClawMachine>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
 
-    const EXAMPLE: &str = "Button A: X+94, Y+34
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 
@@ -95,8 +143,30 @@ Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
     #[test_case(EXAMPLE => 480; "example")]
     fn part1_example(input: &str) -> u64 {
         part1(&parse(input))
     }
+
+    #[test_case(EXAMPLE => 875_318_608_908; "example")]
+    fn part2_example(input: &str) -> u64 {
+        part2(&parse(input))
+    }
+
+    #[test_case((6, 6), (1, 1), (13, 13) => Some(7); "a is more efficient")]
+    #[test_case((2, 2), (1, 1), (4, 4) => Some(4); "b is more efficient")]
+    #[test_case((4, 4), (6, 6), (7, 7) => None; "unreachable on the line")]
+    #[test_case((1, 2), (2, 4), (3, 5) => None; "off the line")]
+    fn collinear_buttons(a: (i64, i64), b: (i64, i64), target: (i64, i64)) -> Option<u64> {
+        search(&ClawMachine {
+            a: a.into(),
+            b: b.into(),
+            target: target.into(),
+        })
+    }
 }
@@ -1,22 +1,56 @@
-use std::io::{self, BufRead};
+use std::{
+    io::{self, BufRead},
+    num::NonZeroUsize,
+};
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    matrix::{Matrix, Torus},
+    params::{Params, ParamsError},
+    pos::Pos,
+    solution::Solution,
+    utils::{self, linalg},
+};
 
-use crate::pos::Pos;
+pub struct Robot {
+    pos: Pos<isize>,
+    vel: Pos<isize>,
+}
 
-struct Robot {
-    pos: Pos<i64>,
-    vel: Pos<i64>,
+impl Robot {
+    /// Where the robot stands after `t` seconds on a `width` by `height` grid wrapping around
+    #[must_use]
+    pub fn position_at(&self, t: usize, width: usize, height: usize) -> Pos<usize> {
+        // each coordinate repeats with the size of its axis, keeping the products small
+        let wrap = |start: isize, vel: isize, size: usize| {
+            let t = isize::try_from(t % size).expect("the grid should fit in an isize");
+            let size = isize::try_from(size).expect("the grid should fit in an isize");
+            (start + vel.rem_euclid(size) * t)
+                .rem_euclid(size)
+                .unsigned_abs()
+        };
+        Pos::new(
+            wrap(self.pos.x, self.vel.x, width),
+            wrap(self.pos.y, self.vel.y, height),
+        )
+    }
 }
 
-struct Info {
+pub struct Info {
     robots: Vec<Robot>,
-    width: i64,
-    height: i64,
+    width: usize,
+    height: usize,
+    /// Whether part 2 asks which frame shows the tree, set with the `browse` parameter
+    browse: bool,
+}
+
+impl Info {
+    #[must_use]
+    pub fn robots(&self) -> &[Robot] {
+        &self.robots
+    }
 }
 
-#[aoc_generator(day14)]
-fn parse(input: &str) -> Info {
+fn parse(input: &str, params: &Params) -> Result<Info, ParamsError> {
     let robots = input
         .lines()
         .map(|line| {
@@ -41,89 +75,170 @@ fn parse(input: &str) -> Info {
             Robot { pos, vel }
         })
         .collect();
-    Info {
+    Ok(Info {
         robots,
-        width: 101,
-        height: 103,
+        // robots cannot move on an empty grid
+        width: params
+            .get_or("width", NonZeroUsize::new(101).unwrap())?
+            .get(),
+        height: params
+            .get_or("height", NonZeroUsize::new(103).unwrap())?
+            .get(),
+        browse: params.get_or("browse", false)?,
+    })
+}
+
+/// How many robots stand on each tile at time `t`
+fn counts_at(info: &Info, t: usize) -> Matrix<usize> {
+    let mut counts = Matrix::new_default(info.width, info.height);
+    for robot in &info.robots {
+        counts[robot.position_at(t, info.width, info.height)] += 1;
     }
+    counts
 }
 
-fn simulate(info: &Info, max_iter: usize, is_part2: bool) -> Vec<Pos<i64>> {
-    let width = info.width;
-    let height = info.height;
-    let mut positions: Vec<_> = info.robots.iter().map(|r| r.pos).collect();
-    for n in 0..max_iter {
-        info.robots
-            .iter()
-            .zip(positions.iter_mut())
-            .for_each(|(Robot { vel, .. }, pos)| {
-                pos.x += vel.x;
-                pos.x = pos.x.rem_euclid(width);
-                pos.y += vel.y;
-                pos.y = pos.y.rem_euclid(height);
-            });
-        if is_part2 {
-            let mut picture = String::new();
-            for y in 0..height {
-                for x in 0..width {
-                    if positions.iter().any(|pos| pos.x == x && pos.y == y) {
-                        picture.push('#');
-                    } else {
-                        picture.push('.');
-                    }
-                }
-                picture.push('\n');
-            }
+/// After how long the robots are all back where they started
+fn period(info: &Info) -> usize {
+    info.width / utils::gcd(info.width, info.height) * info.height
+}
 
-            if picture.contains("#########") {
-                println!("{picture}");
-                println!("{}", n + 1);
-                let mut user_input = String::new();
-                let stdin = io::stdin();
-                stdin.lock().read_line(&mut user_input).unwrap();
-                if !user_input.trim().is_empty() {
-                    return positions;
-                }
-            }
+/// The counts of robots on each tile, from time 0 until the robots loop back
+///
+/// The robots move one step per frame on a torus, instead of being placed anew every time.
+fn frames(info: &Info) -> impl Iterator<Item = Matrix<usize>> + '_ {
+    let empty = Torus::new(Matrix::new_default(info.width, info.height));
+    let mut positions: Vec<_> = info
+        .robots
+        .iter()
+        .map(|robot| empty.wrap(robot.pos))
+        .collect();
+    (0..period(info)).map(move |_| {
+        let mut counts = empty.clone();
+        for (pos, robot) in positions.iter_mut().zip(&info.robots) {
+            counts[*pos] += 1;
+            *pos = counts.offset(*pos, robot.vel);
         }
-    }
-    positions
+        counts.into_inner()
+    })
 }
 
-#[aoc(day14, part1)]
-fn part1(info: &Info) -> usize {
-    let width = info.width;
-    let height = info.height;
-    let positions = simulate(info, 100, false);
+/// The product of the numbers of robots in each quadrant at time `t`, the robots on the middle
+/// lines not counting
+fn safety_factor(info: &Info, t: usize) -> usize {
+    let (width, height) = (info.width, info.height);
+    let counts = counts_at(info, t);
+    let (qw, qh) = (width / 2, height / 2);
     let quadrants = [
-        ((0..width / 2), (0..height / 2)),
-        ((width / 2 + 1..width), (0..height / 2)),
-        ((0..width / 2), (height / 2 + 1..height)),
-        ((width / 2 + 1..width), (height / 2 + 1..height)),
+        counts.view(0, 0, qw, qh),
+        counts.view(width - qw, 0, qw, qh),
+        counts.view(0, height - qh, qw, qh),
+        counts.view(width - qw, height - qh, qw, qh),
     ];
 
-    quadrants
-        .iter()
-        .map(|q| {
-            positions
+    quadrants.iter().map(|q| q.iter().sum::<usize>()).product()
+}
+
+fn part1(info: &Info) -> usize {
+    safety_factor(info, 100)
+}
+
+/// The time in `0..period` when the robots are the most packed along one axis, `axis` picking
+/// that coordinate in a position
+fn tightest_time(robots: &[Robot], period: usize, axis: impl Fn(Pos<isize>) -> isize) -> usize {
+    let modulus = isize::try_from(period).expect("the grid should fit in an isize");
+    (0..modulus)
+        .min_by_key(|&t| {
+            // the variance, scaled by the squared number of robots to stay in integers
+            let (n, sum, sum_sq) = robots
                 .iter()
-                .filter(|pos| q.0.contains(&pos.x) && q.1.contains(&pos.y))
-                .count()
+                .map(|r| (axis(r.pos) + axis(r.vel) * t).rem_euclid(modulus))
+                .fold((0, 0, 0), |(n, sum, sum_sq), v| {
+                    (n + 1, sum + v, sum_sq + v * v)
+                });
+            n * sum_sq - sum.pow(2)
         })
-        .product()
+        .and_then(|t| t.try_into().ok())
+        .unwrap_or_default()
+}
+
+/// The first time the robots draw the Christmas tree
+///
+/// The picture packs most robots together, so both coordinates are at their least spread then.
+/// Each coordinate repeats with the grid size along its axis, so the time of each minimum gives
+/// the time of the picture modulo that size, and both combine with the Chinese remainder
+/// theorem.
+fn find_tree(info: &Info) -> Option<usize> {
+    let tx = tightest_time(&info.robots, info.width, |p| p.x);
+    let ty = tightest_time(&info.robots, info.height, |p| p.y);
+    let [tx, ty, width, height] = [tx, ty, info.width, info.height].map(|n| n as i128);
+    let (t, _) = linalg::crt(tx, width, ty, height)?;
+    t.try_into().ok()
+}
+
+/// Shows every frame with a long line of robots, until one is accepted by typing anything but
+/// an empty line, and returns its time
+fn browse(info: &Info) -> Option<usize> {
+    for (t, counts) in frames(info).enumerate().skip(1) {
+        let picture = Matrix::new(
+            counts
+                .vec
+                .iter()
+                .map(|&count| if count > 0 { '#' } else { '.' }),
+            info.width,
+            info.height,
+        )
+        .to_string();
+        if picture.contains("#########") {
+            // the answer alone goes to stdout
+            eprintln!("{picture}");
+            eprintln!("{t}");
+            let mut user_input = String::new();
+            io::stdin().lock().read_line(&mut user_input).unwrap();
+            if !user_input.trim().is_empty() {
+                return Some(t);
+            }
+        }
+    }
+    None
 }
 
-#[aoc(day14, part2)]
 fn part2(info: &Info) -> usize {
-    simulate(info, 10000, true);
-    0
+    let browsed = if info.browse { browse(info) } else { None };
+    browsed
+        .or_else(|| find_tree(info))
+        .expect("the robots should draw a Christmas tree at some point")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day14;
 
-    const EXAMPLE: &str = "p=0,4 v=3,-3
+impl Day14 {
+    /// The product of the numbers of robots in each quadrant at time `t`
+    #[must_use]
+    pub fn safety_factor(input: &Info, t: usize) -> usize {
+        safety_factor(input, t)
+    }
+}
+
+impl Solution for Day14 {
+    type Input = Info;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, params: &Params) -> Result<Self::Input, ParamsError> {
+        parse(input, params)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "@ width=11 height=7
+p=0,4 v=3,-3
 p=6,3 v=-1,-3
 p=10,3 v=-1,2
 p=2,0 v=2,-1
@@ -137,11 +252,89 @@ p=2,4 v=2,-3
 p=9,5 v=-3,-3
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_name() {
-        let mut info = parse(EXAMPLE);
-        info.width = 11;
-        info.height = 7;
-        assert_eq!(part1(&info), 12);
+        assert_eq!(part1(&Day14::parse(EXAMPLE)), 12);
+    }
+
+    #[test]
+    fn test_zero_size() {
+        let overrides = Params::parse("width=0").unwrap();
+        assert_eq!(
+            Day14::parse_with(EXAMPLE, &overrides).err(),
+            Some(ParamsError::Invalid {
+                key: "width".to_string(),
+                value: "0".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_position_at() {
+        let robot = Robot {
+            pos: Pos::new(2, 4),
+            vel: Pos::new(2, -3),
+        };
+        let expected = [(2, 4), (4, 1), (6, 5), (8, 2), (10, 6), (1, 3)];
+        for (t, (x, y)) in expected.into_iter().enumerate() {
+            assert_eq!(robot.position_at(t, 11, 7), Pos::new(x, y));
+        }
+        assert_eq!(robot.position_at(77 * 1_000_000 + 5, 11, 7), Pos::new(1, 3));
+        // past isize::MAX, and 4 modulo 77
+        assert_eq!(robot.position_at(usize::MAX - 11, 11, 7), Pos::new(10, 6));
+    }
+
+    #[test]
+    fn test_frames() {
+        let info = Day14::parse(EXAMPLE);
+        assert_eq!(period(&info), 77);
+        let frames: Vec<_> = frames(&info).collect();
+        assert_eq!(frames.len(), 77);
+        assert_eq!(frames[100 % 77], counts_at(&info, 100));
+        assert_eq!(Day14::safety_factor(&info, 100 + 77), 12);
+    }
+
+    /// Robots drawing a filled square at `tree_time`, among robots scattered at random
+    fn synthesized(tree_time: isize) -> Info {
+        let (width, height) = (101, 103);
+        let mut state: u64 = 0x2024_1214;
+        let mut random = |modulus: isize| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            isize::try_from(state >> 33).unwrap() % modulus
+        };
+        let tree = (0..15).flat_map(|y| (0..15).map(move |x| Pos::new(40 + x, 30 + y)));
+        let noise: Vec<_> = (0..300)
+            .map(|_| Pos::new(random(width), random(height)))
+            .collect();
+        let robots = tree
+            .chain(noise)
+            .map(|at_tree_time| {
+                let vel = Pos::new(random(201) - 100, random(201) - 100);
+                let pos = Pos::new(
+                    (at_tree_time.x - vel.x * tree_time).rem_euclid(width),
+                    (at_tree_time.y - vel.y * tree_time).rem_euclid(height),
+                );
+                Robot { pos, vel }
+            })
+            .collect();
+        Info {
+            robots,
+            width: 101,
+            height: 103,
+            browse: false,
+        }
+    }
+
+    #[test]
+    fn test_find_tree() {
+        for tree_time in [1, 4_242, 7_583, 10_402] {
+            assert_eq!(part2(&synthesized(tree_time)), tree_time.unsigned_abs());
+        }
     }
 }
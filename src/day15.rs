@@ -1,34 +1,17 @@
 use std::collections::HashSet;
 
-use aoc_runner_derive::{aoc, aoc_generator};
-
-use crate::{matrix::Matrix, pos::Pos};
-
-#[derive(Debug)]
-enum Direction {
-    N,
-    E,
-    S,
-    W,
-}
-
-impl Direction {
-    const fn delta(&self) -> Pos<isize> {
-        match self {
-            Self::N => Pos::new(0, -1),
-            Self::E => Pos::new(1, 0),
-            Self::S => Pos::new(0, 1),
-            Self::W => Pos::new(-1, 0),
-        }
-    }
-}
-
-struct Info {
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    pos::{Direction, Pos},
+    solution::Solution,
+};
+
+pub struct Info {
     warehouse: Matrix<char>,
     instructions: Vec<Direction>,
 }
 
-#[aoc_generator(day15, part1)]
 fn parse(input: &str) -> Info {
     let (warehouse, instructions) = input.split_once("\n\n").unwrap();
     let width = warehouse.lines().next().unwrap().len();
@@ -39,13 +22,7 @@ fn parse(input: &str) -> Info {
     let warehouse = Matrix::new(items, width, height);
     let instructions = instructions
         .chars()
-        .filter_map(|c| match c {
-            '^' => Some(Direction::N),
-            '>' => Some(Direction::E),
-            'v' => Some(Direction::S),
-            '<' => Some(Direction::W),
-            _ => None,
-        })
+        .filter_map(|c| Direction::try_from(c).ok())
         .collect();
     Info {
         warehouse,
@@ -53,7 +30,6 @@ fn parse(input: &str) -> Info {
     }
 }
 
-#[aoc_generator(day15, part2)]
 fn parse2(input: &str) -> Info {
     let (warehouse, instructions) = input.split_once("\n\n").unwrap();
     let width = 2 * warehouse.lines().next().unwrap().len();
@@ -71,13 +47,7 @@ fn parse2(input: &str) -> Info {
     let warehouse = Matrix::new(items, width, height);
     let instructions = instructions
         .chars()
-        .filter_map(|c| match c {
-            '^' => Some(Direction::N),
-            '>' => Some(Direction::E),
-            'v' => Some(Direction::S),
-            '<' => Some(Direction::W),
-            _ => None,
-        })
+        .filter_map(|c| Direction::try_from(c).ok())
         .collect();
     Info {
         warehouse,
@@ -85,58 +55,59 @@ fn parse2(input: &str) -> Info {
     }
 }
 
+/// The content of the warehouse at `pos`, or `None` outside of it
+fn cell(warehouse: &Matrix<char>, pos: Pos<usize>) -> Option<char> {
+    (pos.x < warehouse.width() && pos.y < warehouse.height()).then(|| warehouse[pos])
+}
+
 fn simulate(info: &Info) -> usize {
     let mut warehouse = info.warehouse.clone();
-    let mut robot_pos = warehouse
-        .position(|cell| *cell == '@')
-        .map(|pos| (pos.0.try_into().unwrap(), pos.1.try_into().unwrap()))
-        .map(Pos::from)
-        .unwrap();
+    let mut robot_pos: Pos<usize> = warehouse.position(|cell| *cell == '@').unwrap().into();
     'dir: for d in &info.instructions {
+        let step = |pos: Pos<usize>| pos.checked_add_signed(d.delta());
+        let Some(first) = step(robot_pos) else {
+            continue;
+        };
         let mut wants_to_move: HashSet<_> = std::iter::once(robot_pos).collect();
-        let mut needs_to_be_free: HashSet<_> = std::iter::once(robot_pos + d.delta()).collect();
+        let mut needs_to_be_free: HashSet<_> = std::iter::once(first).collect();
         loop {
             let all_free = needs_to_be_free
                 .iter()
-                .filter_map(|pos| warehouse.get(pos.x, pos.y))
-                .all(|c| *c == '.');
+                .filter_map(|pos| cell(&warehouse, *pos))
+                .all(|c| c == '.');
             if all_free {
                 // to do the move, start from the furthermost positions, say (x,y), and
                 let mut sorted_moving: Vec<_> = wants_to_move.into_iter().collect();
-                sorted_moving.sort_unstable_by_key(|&Pos { x, y }| match d {
-                    Direction::N => y,
-                    Direction::E => -x,
-                    Direction::S => -y,
-                    Direction::W => x,
+                sorted_moving.sort_unstable_by(|a, b| match d {
+                    Direction::N => a.y.cmp(&b.y),
+                    Direction::E => b.x.cmp(&a.x),
+                    Direction::S => b.y.cmp(&a.y),
+                    Direction::W => a.x.cmp(&b.x),
                 });
                 for &pos in &sorted_moving {
-                    let other = pos + d.delta();
-                    let x = pos.x.try_into().unwrap();
-                    let y = pos.y.try_into().unwrap();
-                    let xo = other.x.try_into().unwrap();
-                    let yo = other.y.try_into().unwrap();
-                    warehouse.swap((x, y), (xo, yo));
+                    let other = step(pos).expect("moving cells stay in the warehouse");
+                    warehouse.swap(pos.into(), other.into());
                 }
                 // also remember to update the robot's position
-                robot_pos = robot_pos + d.delta();
+                robot_pos = first;
                 break;
             }
             // otherwise check those that need to be free
             let mut to_switch = Vec::new();
-            for cell_pos in &needs_to_be_free {
-                match warehouse.get(cell_pos.x, cell_pos.y) {
+            for &cell_pos in &needs_to_be_free {
+                match cell(&warehouse, cell_pos) {
                     // if wall: stop everything and nobody moves
                     None | Some('#') => continue 'dir,
                     // if boulder:
                     // say that him and his neighbor wants to move
-                    Some('O') => to_switch.push(*cell_pos),
+                    Some('O') => to_switch.push(cell_pos),
                     Some('[') => {
-                        to_switch.push(*cell_pos);
-                        to_switch.push(*cell_pos + (1, 0).into());
+                        to_switch.push(cell_pos);
+                        to_switch.push(Pos::new(cell_pos.x + 1, cell_pos.y));
                     }
                     Some(']') => {
-                        to_switch.push(*cell_pos);
-                        to_switch.push(*cell_pos + (-1, 0).into());
+                        to_switch.push(cell_pos);
+                        to_switch.push(Pos::new(cell_pos.x - 1, cell_pos.y));
                     }
                     Some('@') => panic!("you are not supposed to be here"),
                     Some('.') => {}
@@ -147,7 +118,9 @@ fn simulate(info: &Info) -> usize {
             for pos in to_switch {
                 wants_to_move.insert(pos);
                 needs_to_be_free.remove(&pos);
-                let moving_into = pos + d.delta();
+                let Some(moving_into) = step(pos) else {
+                    continue 'dir;
+                };
                 if !wants_to_move.contains(&moving_into) {
                     needs_to_be_free.insert(moving_into);
                 }
@@ -161,22 +134,35 @@ fn simulate(info: &Info) -> usize {
         .sum()
 }
 
-#[aoc(day15, part1)]
 fn part1(info: &Info) -> usize {
     simulate(info)
 }
 
-#[aoc(day15, part2)]
 fn part2(info: &Info) -> usize {
     simulate(info)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Info, Info);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok((parse(input), parse2(input)))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(&input.0)
+    }
 
-    const EXAMPLE1: &str = "########
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(&input.1))
+    }
+}
+
+pub const EXAMPLE1: &str = "########
 #..O.O.#
 ##@.O..#
 #...O..#
@@ -188,7 +174,7 @@ mod tests {
 <^^>>>vv<v>>v<<
 ";
 
-    const EXAMPLE2: &str = "##########
+pub const EXAMPLE2: &str = "##########
 #..O..O.O#
 #......O.#
 #.OO..O.O#
@@ -211,6 +197,11 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
     #[test_case(EXAMPLE1 => 2028; "small example")]
     #[test_case(EXAMPLE2 => 10092; "larger example")]
     fn test_part1(input: &str) -> usize {
@@ -1,19 +1,20 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use std::{error::Error, fmt};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-struct Register {
-    a: u32,
-    b: u32,
-    c: u32,
-}
+use crate::{
+    computer::{Combo, Computer, ComputerError, Instruction, Registers},
+    params::{Params, ParamsError},
+    solution::Solution,
+};
+
+/// Instructions run before giving up on a program that does not halt
+const BUDGET: usize = 1 << 20;
 
 #[derive(Debug, Eq, PartialEq)]
-struct Info {
-    register: Register,
-    instr: Vec<u32>,
+pub struct Info {
+    registers: Registers,
+    program: Vec<u8>,
 }
 
-#[aoc_generator(day17)]
 fn parse(input: &str) -> Info {
     let regs = input
         .lines()
@@ -27,14 +28,14 @@ fn parse(input: &str) -> Info {
         .collect::<Vec<_>>();
     let [a, b, c] = regs.try_into().unwrap();
 
-    let instr_text = input
+    let program_text = input
         .split("\n\n")
         .nth(1)
         .and_then(|l| l.split_once(": "))
         .map(|sp| sp.1)
         .unwrap();
 
-    let instr: Vec<_> = instr_text
+    let program: Vec<_> = program_text
         .split(',')
         .map(|s| {
             s.parse()
@@ -43,128 +44,159 @@ fn parse(input: &str) -> Info {
         .collect();
 
     Info {
-        register: Register { a, b, c },
-        instr,
+        registers: Registers { a, b, c },
+        program,
     }
 }
 
-fn combo(operand: u32, register: &Register) -> u32 {
-    match operand {
-        x @ 0..=3 => x,
-        4 => register.a,
-        5 => register.b,
-        6 => register.c,
-        x => panic!("invalid operand {x:?}"),
-    }
+fn part1(info: &Info) -> String {
+    Computer::new(&info.program, info.registers)
+        .run(BUDGET)
+        .unwrap_or_else(|err| panic!("{err}"))
+        .into_iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum OpSideEffect {
-    None,
-    ChangeI(usize),
-    Output(u32),
-    ChangeRegA(u32),
-    ChangeRegB(u32),
-    ChangeRegC(u32),
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuineError {
+    /// The search relies on the program being a loop shifting `A` by 3 bits and outputting once
+    /// per iteration
+    UnsupportedShape(&'static str),
+    Computer(ComputerError),
+    NotFound,
 }
 
-fn operate(opcode: u32, operand: u32, register: &Register) -> OpSideEffect {
-    let wtf = || register.a >> combo(operand, register);
-    match opcode {
-        0 => OpSideEffect::ChangeRegA(wtf()),
-        1 => OpSideEffect::ChangeRegB(register.b ^ operand),
-        2 => OpSideEffect::ChangeRegB(combo(operand, register) % 8),
-        3 => {
-            if register.a != 0 {
-                OpSideEffect::ChangeI(operand as usize)
-            } else {
-                OpSideEffect::None
-            }
+impl fmt::Display for QuineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedShape(reason) => write!(f, "unsupported program shape: {reason}"),
+            Self::Computer(err) => write!(f, "{err}"),
+            Self::NotFound => write!(f, "no value of A makes the program output itself"),
         }
-        4 => OpSideEffect::ChangeRegB(register.b ^ register.c),
-        5 => OpSideEffect::Output(combo(operand, register) % 8),
-        6 => OpSideEffect::ChangeRegB(wtf()),
-        7 => OpSideEffect::ChangeRegC(wtf()),
+    }
+}
+
+impl Error for QuineError {}
 
-        _ => todo!(),
+impl From<ComputerError> for QuineError {
+    fn from(err: ComputerError) -> Self {
+        Self::Computer(err)
     }
 }
 
-fn process(register: &Register, instr: &[u32], cond: impl Fn(&[u32]) -> bool) -> Vec<u32> {
-    let mut register = *register;
-    let mut output = Vec::new();
-
-    let mut i = 0;
-    while i < instr.len() - 1 && cond(&output) {
-        let opcode = instr[i];
-        let operand = instr[i + 1];
-
-        let side_effect = operate(opcode, operand, &register);
-        if let OpSideEffect::ChangeI(next_i) = side_effect {
-            i = next_i;
-        } else {
-            i += 2;
-            match side_effect {
-                OpSideEffect::None => {}
-                OpSideEffect::ChangeI(_) => unreachable!(),
-                OpSideEffect::ChangeRegA(new_a) => register.a = new_a,
-                OpSideEffect::ChangeRegB(new_b) => register.b = new_b,
-                OpSideEffect::ChangeRegC(new_c) => register.c = new_c,
-                OpSideEffect::Output(n) => output.push(n),
+fn check_shape(program: &[u8]) -> Result<(), QuineError> {
+    let instructions = (0..program.len())
+        .step_by(2)
+        .map(|ip| Instruction::fetch(program, ip).map(Option::unwrap))
+        .collect::<Result<Vec<_>, _>>()?;
+    let count = |f: fn(&Instruction) -> bool| instructions.iter().filter(|i| f(i)).count();
+    if instructions.last() != Some(&Instruction::Jnz(0)) {
+        return Err(QuineError::UnsupportedShape(
+            "the program should end with jnz 0",
+        ));
+    }
+    if count(|i| matches!(i, Instruction::Jnz(_))) != 1 {
+        return Err(QuineError::UnsupportedShape(
+            "the only jump should be the last jnz 0",
+        ));
+    }
+    if count(|i| matches!(i, Instruction::Adv(_))) != 1
+        || count(|i| *i == Instruction::Adv(Combo::Literal(3))) != 1
+    {
+        return Err(QuineError::UnsupportedShape(
+            "A should only be changed by a single adv 3",
+        ));
+    }
+    if count(|i| matches!(i, Instruction::Out(_))) != 1 {
+        return Err(QuineError::UnsupportedShape(
+            "the loop should output exactly once",
+        ));
+    }
+    Ok(())
+}
+
+/// The smallest `A` for which the program outputs a copy of itself
+///
+/// Each iteration drops the lowest 3 bits of `A` and outputs a digit depending only on what is
+/// left, so the last digit only depends on the highest 3 bits, and `A` can be built backwards one
+/// octal digit at a time, keeping every prefix matching the end of the program.
+fn find_quine(info: &Info) -> Result<u64, QuineError> {
+    check_shape(&info.program)?;
+    let mut candidates = vec![0];
+    for start in (0..info.program.len()).rev() {
+        let mut next = Vec::new();
+        for a in candidates
+            .iter()
+            .flat_map(|&prefix| (0..8).map(move |digit| prefix << 3 | digit))
+        {
+            let output = Computer::new(
+                &info.program,
+                Registers {
+                    a,
+                    ..info.registers
+                },
+            )
+            .run(BUDGET)?;
+            if output == info.program[start..] {
+                next.push(a);
             }
         }
+        candidates = next;
     }
-    output
+    candidates.into_iter().min().ok_or(QuineError::NotFound)
 }
 
-#[aoc(day17, part1)]
-fn part1(info: &Info) -> String {
-    process(&info.register, &info.instr, |_| true)
-        .into_iter()
-        .map(|n| n.to_string())
-        .collect::<Vec<_>>()
-        .join(",")
+fn part2(info: &Info) -> u64 {
+    find_quine(info).unwrap_or_else(|err| panic!("{err}"))
 }
 
-#[aoc(day17, part2)]
-fn part2(info: &Info) -> u32 {
-    for a in 0.. {
-        //println!("{a}");
-        let this_try_reg = Register { a, ..info.register };
-        let output = process(&this_try_reg, &info.instr, |output| {
-            output.iter().zip(info.instr.iter()).all(|(a, b)| a == b)
-        });
-        if output == info.instr {
-            return a;
-        }
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Info;
+    type Part1 = String;
+    type Part2 = u64;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
     }
-    panic!("How ??")
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
 
-    const EXAMPLE: &str = "Register A: 729
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "Register A: 729
 Register B: 0
 Register C: 0
 
 Program: 0,1,5,4,3,0";
 
-    const EXAMPLE2: &str = "Register A: 2024
+pub const EXAMPLE2: &str = "Register A: 2024
 Register B: 0
 Register C: 0
 
 Program: 0,3,5,4,3,0";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computer::{assemble, disassemble, Event, Register};
+    use test_case::test_case;
+
     #[test]
     fn test_parse() {
         assert_eq!(
             parse(EXAMPLE),
             Info {
-                register: Register { a: 729, b: 0, c: 0 },
-                instr: vec![0, 1, 5, 4, 3, 0]
+                registers: Registers { a: 729, b: 0, c: 0 },
+                program: vec![0, 1, 5, 4, 3, 0]
             }
         );
     }
@@ -176,16 +208,73 @@ Program: 0,3,5,4,3,0";
 
     #[test]
     fn mini_test1() {
-        let register = Register { c: 9, a: 42, b: 42 };
-        let (opcode, operand) = (2, 6);
-        assert_eq!(
-            operate(opcode, operand, &register),
-            OpSideEffect::ChangeRegB(1)
-        );
+        let registers = Registers { c: 9, a: 42, b: 42 };
+        let mut computer = Computer::new(&[2, 6], registers);
+        assert_eq!(computer.step(), Ok(Event::Write(Register::B, 1)));
     }
 
     #[test]
     fn test_part2() {
         assert_eq!(part2(&parse(EXAMPLE2)), 117_440);
     }
+
+    #[test]
+    fn test_quine_of_a_real_shape() {
+        let info = Info {
+            registers: Registers::default(),
+            program: vec![2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0],
+        };
+        let a = find_quine(&info).unwrap();
+        let output = Computer::new(
+            &info.program,
+            Registers {
+                a,
+                ..info.registers
+            },
+        )
+        .run(BUDGET)
+        .unwrap();
+        assert_eq!(output, info.program);
+    }
+
+    #[test]
+    fn test_unsupported_shape() {
+        assert_eq!(
+            find_quine(&parse(EXAMPLE)),
+            Err(QuineError::UnsupportedShape(
+                "A should only be changed by a single adv 3"
+            ))
+        );
+        let info = Info {
+            registers: Registers::default(),
+            program: vec![0, 3, 5, 4],
+        };
+        assert_eq!(
+            find_quine(&info),
+            Err(QuineError::UnsupportedShape(
+                "the program should end with jnz 0"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_assembled_program() {
+        let source = "
+            adv 3
+            out A
+            jnz 0
+        ";
+        let program: Vec<_> = assemble(source)
+            .unwrap()
+            .iter()
+            .map(u8::to_string)
+            .collect();
+        let input = format!(
+            "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+            program.join(",")
+        );
+        assert_eq!(parse(&input), parse(EXAMPLE2));
+        let listing = disassemble(&parse(EXAMPLE).program).unwrap();
+        assert_eq!(assemble(&listing).unwrap(), parse(EXAMPLE).program);
+    }
 }
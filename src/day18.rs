@@ -1,68 +1,79 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::num::NonZeroU32;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    pos::Pos,
+    search,
+    solution::Solution,
+    sparse_grid::SparseGrid,
+};
 
-use crate::pos::Pos;
-
-struct Info {
-    walls: Vec<Pos<i32>>,
-    side_len: i32,
+pub struct Info {
+    walls: Vec<Pos<i64>>,
+    /// When each wall falls, by position
+    fallen_at: SparseGrid<usize>,
+    side_len: i64,
+    /// How many bytes have fallen in part 1
+    bytes: usize,
 }
 
-#[aoc_generator(day18)]
-fn parse(input: &str) -> Info {
-    let side_len = 70;
-    let walls = input
+fn parse(input: &str, params: &Params) -> Result<Info, ParamsError> {
+    let walls: Vec<_> = input
         .lines()
         .map(|l| l.split_once(',').unwrap())
         .map(|(x, y)| Pos::new(x.parse().unwrap(), y.parse().unwrap()))
         .collect();
-    Info { walls, side_len }
+    let mut fallen_at = SparseGrid::new();
+    for (i, &wall) in walls.iter().enumerate().rev() {
+        fallen_at.insert(wall, i);
+    }
+    let bytes = params.get_or("bytes", 1024)?;
+    if bytes > walls.len() {
+        return Err(ParamsError::Invalid {
+            key: "bytes".to_string(),
+            value: bytes.to_string(),
+        });
+    }
+    Ok(Info {
+        walls,
+        fallen_at,
+        side_len: params
+            .get_or("side_len", NonZeroU32::new(70).unwrap())?
+            .get()
+            .into(),
+        bytes,
+    })
 }
 
 fn bfs(info: &Info, until: usize) -> Option<usize> {
     let start_pos = Pos::new(0, 0);
     let end_pos = Pos::new(info.side_len, info.side_len);
-    let walls = &info.walls[..until];
-    let mut dists: HashMap<Pos<i32>, usize> = HashMap::new();
-    dists.insert(start_pos, 0);
-    let mut to_visit = VecDeque::from([start_pos]);
-    let mut visited = HashSet::new();
-    while let Some(pos) = to_visit.pop_front() {
-        if visited.contains(&pos) {
-            continue;
-        }
-        visited.insert(pos);
-        let neighbors = [
-            (pos.x + 1, pos.y),
-            (pos.x - 1, pos.y),
-            (pos.x, pos.y + 1),
-            (pos.x, pos.y - 1),
-        ]
-        .into_iter()
-        .map(|(x, y)| Pos::new(x, y))
-        .filter(|n| {
-            !visited.contains(n)
-                && !walls.contains(n)
-                && (0..=info.side_len).contains(&n.x)
-                && (0..=info.side_len).contains(&n.y)
-        });
-        let dist = dists[&pos] + 1;
-        for n in neighbors {
-            let dist_n = dists.entry(n).or_insert(usize::MAX);
-            *dist_n = dist.min(*dist_n);
-            to_visit.push_back(n);
-        }
-    }
-    dists.get(&end_pos).copied()
+    let is_wall = |pos: Pos<i64>| info.fallen_at.get(pos).is_some_and(|&i| i < until);
+    search::bfs(
+        start_pos,
+        |pos| {
+            [
+                Pos::new(pos.x + 1, pos.y),
+                Pos::new(pos.x - 1, pos.y),
+                Pos::new(pos.x, pos.y + 1),
+                Pos::new(pos.x, pos.y - 1),
+            ]
+            .into_iter()
+            .filter(|n| {
+                !is_wall(*n)
+                    && (0..=info.side_len).contains(&n.x)
+                    && (0..=info.side_len).contains(&n.y)
+            })
+        },
+        |pos| *pos == end_pos,
+    )
+    .goal_cost()
 }
 
-#[aoc(day18, part1)]
 fn part1(info: &Info) -> usize {
-    bfs(info, 1024).expect("there should be a path to the end")
+    bfs(info, info.bytes).expect("there should be a path to the end")
 }
 
-#[aoc(day18, part2)]
 fn part2(info: &Info) -> String {
     (0..info.walls.len())
         .find(|&i| bfs(info, i).is_none())
@@ -71,18 +82,28 @@ fn part2(info: &Info) -> String {
         .expect("There should be a wall that cuts off the path")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day18;
 
-    #[test]
-    fn test_bfs() {
-        let mut info = parse(EXAMPLE);
-        info.side_len = 6;
-        assert_eq!(bfs(&info, 12), Some(22));
+impl Solution for Day18 {
+    type Input = Info;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse_body(input: &str, params: &Params) -> Result<Self::Input, ParamsError> {
+        parse(input, params)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
     }
 
-    const EXAMPLE: &str = "5,4
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "@ side_len=6 bytes=12
+5,4
 4,2
 4,5
 3,0
@@ -109,10 +130,39 @@ mod tests {
 2,0
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs() {
+        assert_eq!(bfs(&Day18::parse(EXAMPLE), 12), Some(22));
+        assert_eq!(part1(&Day18::parse(EXAMPLE)), 22);
+    }
+
     #[test]
     fn test_part2() {
-        let mut info = parse(EXAMPLE);
-        info.side_len = 6;
-        assert_eq!(&part2(&info), "6,1");
+        assert_eq!(&part2(&Day18::parse(EXAMPLE)), "6,1");
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let error =
+            |overrides| Day18::parse_with(EXAMPLE, &Params::parse(overrides).unwrap()).err();
+        assert_eq!(
+            error("side_len=0"),
+            Some(ParamsError::Invalid {
+                key: "side_len".to_string(),
+                value: "0".to_string()
+            })
+        );
+        assert_eq!(
+            error("bytes=26"),
+            Some(ParamsError::Invalid {
+                key: "bytes".to_string(),
+                value: "26".to_string()
+            })
+        );
+        assert!(error("bytes=25").is_none());
     }
 }
@@ -1,6 +1,15 @@
 use std::{collections::HashMap, string::ToString};
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+    utils::par_iter,
+};
+
+#[cfg(not(feature = "parallel"))]
+use crate::utils::MapInit;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 
 fn aux1(s: &str, towels: &Vec<String>) -> bool {
     if s.is_empty() {
@@ -34,12 +43,11 @@ fn aux2<'a>(s: &'a str, towels: &Vec<String>, memo: &mut HashMap<&'a str, usize>
     res
 }
 
-struct Info {
+pub struct Info {
     towels: Vec<String>,
     designs: Vec<String>,
 }
 
-#[aoc_generator(day19)]
 fn parse(input: &str) -> Info {
     let (towels, designs) = input.split_once("\n\n").unwrap();
     let towels = towels.split(", ").map(ToString::to_string).collect();
@@ -47,28 +55,42 @@ fn parse(input: &str) -> Info {
     Info { towels, designs }
 }
 
-#[aoc(day19, part1)]
 fn part1(info: &Info) -> usize {
-    info.designs
-        .iter()
+    par_iter!(&info.designs)
         .filter(|design| aux1(design, &info.towels))
         .count()
 }
 
-#[aoc(day19, part2)]
 fn part2(info: &Info) -> usize {
-    let mut memo = HashMap::new();
-    info.designs
-        .iter()
-        .map(|design| aux2(design, &info.towels, &mut memo))
+    // designs share suffixes, so the memo is kept across designs, one per thread
+    par_iter!(&info.designs)
+        .map_init(HashMap::new, |memo, design| {
+            aux2(design, &info.towels, memo)
+        })
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day19;
 
-    const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
+impl Solution for Day19 {
+    type Input = Info;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
 
 brwrr
 bggr
@@ -80,6 +102,10 @@ brgr
 bbrgwb
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
         assert_eq!(part1(&parse(EXAMPLE)), 6);
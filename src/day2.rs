@@ -1,6 +1,12 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+    utils::par_iter,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 
-#[aoc_generator(day2)]
 fn gen(input: &str) -> Vec<Vec<i32>> {
     input
         .lines()
@@ -34,39 +40,52 @@ fn is_safe_diff<I: IntoIterator<Item = i32> + Clone>(diffs: I) -> bool {
         || diffs.into_iter().all(|d| si.contains(&-d))
 }
 
-#[aoc(day2, part1)]
 fn p1(reports: &[Vec<i32>]) -> usize {
-    reports.iter().filter(|r| is_safe(r)).count()
+    par_iter!(reports).filter(|r| is_safe(r)).count()
 }
 
-#[aoc(day2, part2)]
 fn p2(reports: &[Vec<i32>]) -> usize {
-    reports.iter().filter(|r| is_safe_skipping(r)).count()
+    par_iter!(reports).filter(|r| is_safe_skipping(r)).count()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Day2;
 
-    #[test]
-    fn test_p1() {
-        let input = "7 6 4 2 1
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(gen(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(p2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!(2, p1(&gen(input)));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_p1() {
+        assert_eq!(2, p1(&gen(EXAMPLE)));
     }
 
     #[test]
     fn test_p2() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        assert_eq!(4, p2(&gen(input)));
+        assert_eq!(4, p2(&gen(EXAMPLE)));
     }
 }
@@ -1,18 +1,26 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::{collections::HashMap, convert::Infallible};
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    pos::Pos,
+    search,
+    solution::Solution,
+    utils::{self, par_iter},
+};
 
-use crate::{matrix::Matrix, pos::Pos, utils};
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 
-struct Info {
+pub struct Info {
     maze: Matrix<char>,
-    count_if_more_than: usize,
+    /// The least time a cheat has to save to be counted
+    min_save: usize,
     start: Pos<isize>,
     end: Pos<isize>,
 }
 
-#[aoc_generator(day20)]
-fn parse(input: &str) -> Info {
+fn parse(input: &str, params: &Params) -> Result<Info, ParamsError> {
     let mut start = None;
     let mut end = None;
     let maze = utils::parse_grid_using_pos(input, |pos, c| {
@@ -29,119 +37,94 @@ fn parse(input: &str) -> Info {
         }
     });
 
-    Info {
+    Ok(Info {
         start: start.unwrap(),
         end: end.unwrap(),
-        count_if_more_than: 100,
+        min_save: params.get_or("min_save", 100)?,
         maze,
-    }
+    })
 }
 
 fn normal_path(info: &Info) -> Vec<Pos<isize>> {
     // find the path without cheats
-    let mut visited = HashSet::new();
-    visited.insert(info.start);
-    let mut path = vec![info.start];
-    let mut next = info.start;
-
-    while next != info.end {
-        visited.insert(next);
-        let neighbors = [
-            Pos::new(next.x + 1, next.y),
-            Pos::new(next.x - 1, next.y),
-            Pos::new(next.x, next.y + 1),
-            Pos::new(next.x, next.y - 1),
-        ];
-
-        next = *neighbors
-            .iter()
-            .find(|n| {
-                info.maze.get(n.x, n.y).is_some_and(|cell| *cell == '.') && !visited.contains(*n)
-            })
-            .unwrap();
-        path.push(next);
-    }
-    path
+    search::bfs(
+        info.start,
+        |p| {
+            [
+                Pos::new(p.x + 1, p.y),
+                Pos::new(p.x - 1, p.y),
+                Pos::new(p.x, p.y + 1),
+                Pos::new(p.x, p.y - 1),
+            ]
+            .into_iter()
+            .filter(|n| info.maze.get(n.x, n.y).is_some_and(|cell| *cell == '.'))
+        },
+        |p| *p == info.end,
+    )
+    .shortest_path()
+    .expect("there should be a path from the start to the end")
 }
 
-fn time_saves(info: &Info, path: &[Pos<isize>], max_dist: isize) -> HashMap<usize, i32> {
-    // for each node look at where we can go with a cheat
-    let mut time_saves = HashMap::new();
-    for (picosec_start, &p) in path.iter().enumerate() {
-        println!("{p:?} ---");
-        let mut cheatables = HashSet::new();
-        let mut queue = VecDeque::from([p]);
-        let mut visited = HashSet::new();
-        let deltas = [
-            Pos::new(1, 0),
-            Pos::new(-1, 0),
-            Pos::new(0, 1),
-            Pos::new(0, -1),
-        ];
-
-        while let Some(pp) = queue.pop_front() {
-            if visited.contains(&pp) {
-                continue;
-            }
-            visited.insert(pp);
-            if info.maze.get(pp.x, pp.y).is_some_and(|c| *c == '.') {
-                cheatables.insert(pp);
-            }
-            for d in deltas {
-                let n = pp + d;
-                if !visited.contains(&n) && (p - n).l1_norm() <= max_dist {
-                    println!("dist: {}, to {n:?}", (p - n).l1_norm());
-                    queue.push_back(n);
-                }
-            }
-        }
-        //for d in deltas {
-        //    let n = p + d;
-        //    if info.maze.get(n.x, n.y).is_some_and(|c| *c == '#') {
-        //        let nn = p + d * 2;
-        //        if info.maze.get(nn.x, nn.y).is_some_and(|c| *c == '.') {
-        //            cheatables.insert(nn);
-        //        }
-        //    }
-        //}
-        //
-        //println!("{p:?} can cheat to {cheatables:?}");
-        //println!("{cheatables:?}");
-
-        // find out how much time was saved
-        for cheatable in cheatables {
-            let cheat_len = (p - cheatable).l1_norm().try_into().unwrap();
-            let picosec_arrival = path
-                .iter()
-                .position(|pp| *pp == cheatable)
-                .expect("a cheatable at this point should be in the path");
-            if let Some(time_saved) = picosec_arrival
-                .checked_sub(picosec_start)
-                .and_then(|t| t.checked_sub(cheat_len))
-            {
-                *time_saves.entry(time_saved).or_insert(0) += 1;
-            }
+/// When each tile of the path is reached
+fn picosec_at(path: &[Pos<isize>]) -> HashMap<Pos<isize>, usize> {
+    path.iter().enumerate().map(|(i, &p)| (p, i)).collect()
+}
+
+/// The time saved by each cheat of at most `max_dist` picoseconds, starting `picosec_start`
+/// picoseconds along the path
+fn cheats_from<'a>(
+    path: &[Pos<isize>],
+    picosec_at: &'a HashMap<Pos<isize>, usize>,
+    picosec_start: usize,
+    max_dist: isize,
+) -> impl Iterator<Item = usize> + 'a {
+    let p = path[picosec_start];
+    p.l1_ball(max_dist).filter_map(move |cheatable| {
+        let &picosec_arrival = picosec_at.get(&cheatable)?;
+        let cheat_len = p.manhattan_to(&cheatable).unsigned_abs();
+        if cheat_len < 2 {
+            return None;
         }
-    }
-    time_saves
+        picosec_arrival
+            .checked_sub(picosec_start + cheat_len)
+            .filter(|t| *t > 0)
+    })
 }
 
-#[aoc(day20, part1)]
-fn part1(info: &Info) -> i32 {
+fn count_good_cheats(info: &Info, max_dist: isize) -> usize {
     let path = normal_path(info);
-    let time_saves = time_saves(info, &path, 2);
-    time_saves
-        .into_iter()
-        .filter(|(t, _)| *t >= info.count_if_more_than)
-        .map(|(_, c)| c)
+    let picosec_at = picosec_at(&path);
+    par_iter!(0..path.len())
+        .map(|picosec_start| {
+            cheats_from(&path, &picosec_at, picosec_start, max_dist)
+                .filter(|t| *t >= info.min_save)
+                .count()
+        })
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn part1(info: &Info) -> usize {
+    count_good_cheats(info, 2)
+}
+
+pub struct Day20;
 
-    const EXAMPLE: &str = "###############
+impl Solution for Day20 {
+    type Input = Info;
+    type Part1 = usize;
+    type Part2 = Infallible;
+
+    fn parse_body(input: &str, params: &Params) -> Result<Self::Input, ParamsError> {
+        parse(input, params)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+}
+
+pub const EXAMPLE: &str = "@ min_save=50
+###############
 #...#...#.....#
 #.#.#.#.#.###.#
 #S#...#.#.#...#
@@ -158,16 +141,31 @@ mod tests {
 ###############
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How many cheats of at most `max_dist` picoseconds save each amount of time
+    fn time_saves(path: &[Pos<isize>], max_dist: isize) -> HashMap<usize, i32> {
+        let picosec_at = picosec_at(path);
+        let mut time_saves = HashMap::new();
+        for picosec_start in 0..path.len() {
+            for time_saved in cheats_from(path, &picosec_at, picosec_start, max_dist) {
+                *time_saves.entry(time_saved).or_insert(0) += 1;
+            }
+        }
+        time_saves
+    }
+
     #[test]
     fn test_normal_path() {
-        let result = normal_path(&parse(EXAMPLE));
+        let result = normal_path(&Day20::parse(EXAMPLE));
         assert_eq!(result.len(), 85);
     }
 
     #[test]
     fn test_part1() {
         let expected_saves: HashMap<_, _> = [
-            (0, 84),
             (2, 14),
             (4, 14),
             (6, 2),
@@ -182,8 +180,8 @@ mod tests {
         ]
         .into_iter()
         .collect();
-        let info = &parse(EXAMPLE);
-        let result = time_saves(info, &normal_path(info), 2);
+        let result = time_saves(&normal_path(&Day20::parse(EXAMPLE)), 2);
         assert_eq!(expected_saves, result);
+        assert_eq!(part1(&Day20::parse(EXAMPLE)), 1);
     }
 }
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use aoc_runner_derive::aoc;
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
 
-#[aoc(day23, part1)]
 fn part1(input: &str) -> usize {
     let triangles = input.lines().enumerate().flat_map(|(i, line)| {
         let (left, right) = line
@@ -47,7 +49,6 @@ fn part1(input: &str) -> usize {
         .count()
 }
 
-#[aoc(day23, part2)]
 fn part2(input: &str) -> String {
     let mut adj_list = HashMap::new();
     for line in input.lines() {
@@ -75,11 +76,27 @@ fn part2(input: &str) -> String {
     biggest_clique.join(",")
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
 
-    const EXAMPLE: &str = "kh-tc
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "kh-tc
 qp-kh
 de-cg
 ka-co
@@ -112,6 +129,10 @@ wh-qp
 tb-vc
 td-yn";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
         assert_eq!(part1(EXAMPLE), 7);
@@ -1,8 +1,12 @@
 use std::sync::LazyLock;
 
-use aoc_runner_derive::aoc;
 use regex::Regex;
 
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
+
 static RE_MUL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"mul\((\d+),(\d+)\)").unwrap());
 #[allow(clippy::trivial_regex)] // because although some regexes are trivial we are using it
                                 // multiple times, therefore this lint does not apply
@@ -10,7 +14,6 @@ static RE_DO: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"do\(\)").unwrap())
 #[allow(clippy::trivial_regex)]
 static RE_DONT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"don't\(\)").unwrap());
 
-#[aoc(day3, part1)]
 fn p1(input: &str) -> u64 {
     RE_MUL
         .captures_iter(input)
@@ -26,7 +29,6 @@ enum Token {
     Mul(u64, u64),
 }
 
-#[aoc(day3, part2)]
 fn p2(input: &str) -> u64 {
     // find all the tokens and their positions
     let mul_token_positions: Vec<_> = RE_MUL
@@ -72,19 +74,43 @@ fn p2(input: &str) -> u64 {
     sum
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(p2(input))
+    }
+}
+
+pub const EXAMPLE1: &str =
+    "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+pub const EXAMPLE2: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
     fn test_p1() {
-        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!(161, p1(input));
+        assert_eq!(161, p1(EXAMPLE1));
     }
 
     #[test]
     fn test_p2() {
-        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!(48, p2(input));
+        assert_eq!(48, p2(EXAMPLE2));
     }
 }
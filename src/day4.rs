@@ -1,8 +1,9 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    solution::Solution,
+};
 
-use crate::matrix::Matrix;
-
-#[aoc_generator(day4)]
 fn parse(input: &str) -> Matrix<char> {
     let width = input.lines().next().map(str::len).unwrap();
     let height = input.lines().count();
@@ -10,88 +11,75 @@ fn parse(input: &str) -> Matrix<char> {
     Matrix::new(items, width, height)
 }
 
-#[aoc(day4, part1)]
-fn p1(letters: &Matrix<char>) -> usize {
-    let deltas = [
-        (1, 0),
-        (-1, 0),
-        (0, 1),
-        (0, -1),
-        (1, 1),
-        (-1, 1),
-        (1, -1),
-        (-1, -1),
-    ];
-    let x_pos = letters
-        .iter_pos()
-        .filter(|(_, letter)| **letter == 'X')
-        .map(|(pos, _)| pos);
+const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
 
-    // for X, we look at the deltas
-    // for delta we want (1, M) -> (2, A) -> (3, S)
-    // count the number of times the chain gets to the end
-    let chain = [(1, 'M'), (2, 'A'), (3, 'S')];
-    x_pos
-        .into_iter()
-        .map(|x_pos| {
-            deltas
-                .iter()
-                .filter(|delta| {
-                    chain.iter().all(|&(coeff, wanting)| {
-                        let new_pos = (
-                            i32::try_from(x_pos.0).unwrap() + delta.0 * coeff,
-                            i32::try_from(x_pos.1).unwrap() + delta.1 * coeff,
-                        );
-                        letters
-                            .get(new_pos.0.try_into().unwrap(), new_pos.1.try_into().unwrap())
-                            .is_some_and(|&letter| letter == wanting)
-                    })
-                })
-                .count()
-        })
+/// Counts the XMAS written left to right, or diagonally towards the bottom right
+fn count_east_and_south_east(letters: &Matrix<char>) -> usize {
+    let horizontal: usize = letters
+        .rows()
+        .map(|row| row.windows(4).filter(|w| *w == XMAS).count())
+        .sum();
+    let diagonal = (0..letters.height().saturating_sub(3))
+        .flat_map(|y| (0..letters.width().saturating_sub(3)).map(move |x| letters.view(x, y, 4, 4)))
+        .filter(|window| (0..4).all(|i| window[(i, i)] == XMAS[i]))
+        .count();
+    horizontal + diagonal
+}
+
+fn p1(letters: &Matrix<char>) -> usize {
+    // each quarter turn maps the two directions we scan onto two other ones,
+    // so the four rotations cover all eight directions exactly once
+    letters
+        .rotations()
+        .map(|rotated| count_east_and_south_east(&rotated))
         .sum()
 }
 
-#[aoc(day4, part2)]
 fn p2(letters: &Matrix<char>) -> usize {
     // for A we look at the cross neighbors
     // if they are all M and S and M's are not crossed, we gucci, we tamagucci
-    let a_pos = letters
-        .iter_pos()
-        .filter(|(_, letter)| **letter == 'A')
-        .map(|(pos, _)| pos);
+    let windows = (0..letters.height().saturating_sub(2)).flat_map(|y| {
+        (0..letters.width().saturating_sub(2)).map(move |x| letters.view(x, y, 3, 3))
+    });
 
-    let deltas = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
-    a_pos
-        .filter(|a_pos| {
-            let nbs: Vec<_> = deltas
-                .iter()
-                .map(|d| {
-                    (
-                        isize::try_from(a_pos.0).unwrap() + d.0,
-                        isize::try_from(a_pos.1).unwrap() + d.1,
-                    )
-                })
-                .collect();
-            let all_good_letters = nbs
-                .iter()
-                .all(|&(nx, ny)| matches!(letters.get(nx, ny), Some('M' | 'S')));
-            let no_cross_letters = (letters.get(nbs[0].0, nbs[0].1)
-                != letters.get(nbs[3].0, nbs[3].1))
-                && letters.get(nbs[1].0, nbs[1].1) != letters.get(nbs[2].0, nbs[2].1);
+    windows
+        .filter(|window| window[(1, 1)] == 'A')
+        .filter(|window| {
+            let corners = [
+                window[(0, 0)],
+                window[(2, 0)],
+                window[(0, 2)],
+                window[(2, 2)],
+            ];
+            let all_good_letters = corners.iter().all(|c| matches!(c, 'M' | 'S'));
+            let no_cross_letters = corners[0] != corners[3] && corners[1] != corners[2];
 
             all_good_letters && no_cross_letters
         })
         .count()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Day4;
 
-    #[test]
-    fn test_p1() {
-        let input = "MMMSXXMASM
+impl Solution for Day4 {
+    type Input = Matrix<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(p2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -101,20 +89,17 @@ SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
-        assert_eq!(18, p1(&parse(input)));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_p1() {
+        assert_eq!(18, p1(&parse(EXAMPLE)));
     }
     #[test]
     fn test_p2() {
-        let input = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!(9, p2(&parse(input)));
+        assert_eq!(9, p2(&parse(EXAMPLE)));
     }
 }
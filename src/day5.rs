@@ -1,14 +1,17 @@
-use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashMap, HashSet};
 
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
+
 type Rules = HashMap<u32, HashSet<u32>>;
 
-struct Instructions {
+pub struct Instructions {
     rules: Rules,
     updates: Vec<Vec<u32>>,
 }
 
-#[aoc_generator(day5)]
 fn parse(input: &str) -> Instructions {
     let (rules_part, updates_part) = input.split_once("\n\n").unwrap();
     let mut rules = HashMap::new();
@@ -48,7 +51,6 @@ fn is_sorted(update: &[u32], rules: &Rules) -> bool {
     })
 }
 
-#[aoc(day5, part1)]
 fn part1(input: &Instructions) -> u32 {
     input
         .updates
@@ -77,7 +79,6 @@ fn sorted(update: &[u32], rules: &Rules) -> Vec<u32> {
     l
 }
 
-#[aoc(day5, part2)]
 fn part2(input: &Instructions) -> u32 {
     input
         .updates
@@ -88,11 +89,27 @@ fn part2(input: &Instructions) -> u32 {
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Instructions;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
 
-    const EXAMPLE: &str = "47|53
+pub const EXAMPLE: &str = "47|53
 97|13
 97|61
 97|47
@@ -121,6 +138,10 @@ mod tests {
 61,13,29
 97,13,75,29,47";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part1_example() {
         assert_eq!(part1(&parse(EXAMPLE)), 143);
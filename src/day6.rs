@@ -1,153 +1,293 @@
-use aoc_runner_derive::{aoc, aoc_generator};
-use std::collections::HashSet;
+use crate::{
+    matrix::Matrix,
+    params::{Params, ParamsError},
+    pos::{Direction, Pos},
+    solution::Solution,
+    utils::{self, par_iter},
+};
 
-type Tiles = HashSet<TileAndDirection>;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-enum Direction {
-    N,
-    E,
-    S,
-    W,
+#[derive(Debug, Clone)]
+pub struct Info {
+    obstacles: Matrix<bool>,
+    /// For each tile and direction, where the guard walking that way stops in front of the next
+    /// obstacle, `None` if the guard leaves the area first
+    jumps: Matrix<[Option<Pos<usize>>; 4]>,
+    start: Pos<usize>,
+    start_dir: Direction,
+    /// Whether part 2 prints the loops it finds to stderr, set with the `show_loops` parameter
+    show_loops: bool,
 }
 
-#[derive(Eq, Hash, PartialEq)]
-struct TileAndDirection {
-    pos: (i32, i32),
-    dir_visited: Direction,
+impl Info {
+    /// The tile in front of `pos`, if it is in the area
+    fn ahead(&self, pos: Pos<usize>, dir: Direction) -> Option<Pos<usize>> {
+        pos.checked_add_signed(dir.delta())
+            .filter(|p| p.x < self.obstacles.width() && p.y < self.obstacles.height())
+    }
+
+    /// Where the guard walking from `pos` towards `dir` stops, with an extra obstacle at
+    /// `obstacle`
+    fn jump(&self, pos: Pos<usize>, dir: Direction, obstacle: Pos<usize>) -> Option<Pos<usize>> {
+        let stop = self.jumps[pos][dir as usize];
+        let delta = dir.delta();
+        let distance = |to: Pos<usize>| {
+            let offset = to.to_isize() - pos.to_isize();
+            (offset.x * delta.y == offset.y * delta.x).then_some(offset.dot(&delta))
+        };
+        match distance(obstacle) {
+            // the extra obstacle is ahead, and nearer than the first one
+            Some(d) if d > 0 && stop.is_none_or(|stop| distance(stop).is_some_and(|s| d <= s)) => {
+                Some((pos.to_isize() + delta * (d - 1)).to_usize())
+            }
+            _ => stop,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-struct Info {
-    width: i32,
-    height: i32,
-    wall_pos: Vec<(i32, i32)>,
-    s_pos: (i32, i32),
-    s_dir: Direction,
+fn jump_tables(obstacles: &Matrix<bool>) -> Matrix<[Option<Pos<usize>>; 4]> {
+    let (width, height) = (obstacles.width(), obstacles.height());
+    let mut jumps: Matrix<[Option<Pos<usize>>; 4]> = Matrix::new_default(width, height);
+    let row_major = (0..height).flat_map(|y| (0..width).map(move |x| Pos::new(x, y)));
+    for dir in Direction::ALL {
+        // the tile ahead has to be known first, it comes first when walking north or west
+        let order: Box<dyn Iterator<Item = Pos<usize>>> = match dir {
+            Direction::N | Direction::W => Box::new(row_major.clone()),
+            Direction::S | Direction::E => Box::new(row_major.clone().rev()),
+        };
+        for pos in order {
+            let ahead = pos
+                .checked_add_signed(dir.delta())
+                .filter(|p| p.x < width && p.y < height);
+            jumps[pos][dir as usize] = match ahead {
+                None => None,
+                Some(ahead) if obstacles[ahead] => Some(pos),
+                Some(ahead) => jumps[ahead][dir as usize],
+            };
+        }
+    }
+    jumps
+}
+
+/// Where the guard starts walking in circles
+#[derive(Debug, PartialEq, Eq)]
+struct Cycle {
+    /// The first step of the loop, and its index in the steps of the walk
+    entry: (Pos<usize>, Direction),
+    entry_step: usize,
+    length: usize,
 }
 
 struct VisitedInfo {
-    tiles: Tiles,
-    does_loop: bool,
+    /// Every tile the guard stood on in order, with the direction faced when leaving it, each
+    /// step of a loop only appearing once
+    steps: Vec<(Pos<usize>, Direction)>,
+    cycle: Option<Cycle>,
 }
 
 impl VisitedInfo {
-    fn unique_tiles(&self) -> HashSet<(i32, i32)> {
-        self.tiles.iter().map(|t| t.pos).collect()
+    fn unique_tiles(&self, info: &Info) -> usize {
+        let mut seen = Matrix::new_default(info.obstacles.width(), info.obstacles.height());
+        for &(pos, _) in &self.steps {
+            seen[pos] = true;
+        }
+        seen.vec.into_iter().filter(|&seen| seen).count()
     }
-}
 
-fn visited(info: &Info) -> VisitedInfo {
-    let mut visited = HashSet::new();
-    let mut pos = info.s_pos;
-    let mut dir = info.s_dir;
-    let in_bounds =
-        |(x, y): (i32, i32)| (0..info.width).contains(&x) && (0..info.height).contains(&y);
-    while in_bounds(pos) {
-        let tile_and_direction = TileAndDirection {
-            pos,
-            dir_visited: dir,
+    const fn does_loop(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// The map with the path of the guard drawn over it as in the puzzle, `|` and `-` for the
+    /// tiles walked vertically and horizontally, `+` for both, and `O` for the extra obstacle
+    fn render(&self, info: &Info, obstacle: Option<Pos<usize>>) -> String {
+        let (width, height) = (info.obstacles.width(), info.obstacles.height());
+        let mut walked: Matrix<(bool, bool)> = Matrix::new_default(width, height);
+        for &(pos, dir) in &self.steps {
+            let (vertical, horizontal) = &mut walked[pos];
+            *vertical |= dir.is_vertical();
+            *horizontal |= !dir.is_vertical();
+        }
+        let tiles = (0..height).flat_map(|y| (0..width).map(move |x| Pos::new(x, y)));
+        let tile = |pos: Pos<usize>| match walked[pos] {
+            _ if info.obstacles[pos] => '#',
+            _ if Some(pos) == obstacle => 'O',
+            (true, true) => '+',
+            (true, false) => '|',
+            (false, true) => '-',
+            (false, false) => '.',
+        };
+        let mut picture = Matrix::new(tiles.map(tile), width, height);
+        picture[info.start] = match info.start_dir {
+            Direction::N => '^',
+            Direction::E => '>',
+            Direction::S => 'v',
+            Direction::W => '<',
         };
-        if visited.contains(&tile_and_direction) {
+        picture.to_string()
+    }
+}
+
+/// Bit flags of the directions the guard faced on each tile
+fn seen_directions(info: &Info) -> Matrix<u8> {
+    Matrix::new_default(info.obstacles.width(), info.obstacles.height())
+}
+
+/// Walks the guard tile by tile until leaving the area or looping, with an optional extra
+/// obstacle
+fn visited_with(info: &Info, obstacle: Option<Pos<usize>>) -> VisitedInfo {
+    let mut steps = Vec::new();
+    let mut seen = seen_directions(info);
+    let (mut pos, mut dir) = (info.start, info.start_dir);
+    loop {
+        if seen[pos] & 1 << dir as u8 != 0 {
+            let entry_step = steps.iter().position(|&step| step == (pos, dir)).unwrap();
+            let cycle = Cycle {
+                entry: (pos, dir),
+                entry_step,
+                length: steps.len() - entry_step,
+            };
             return VisitedInfo {
-                tiles: visited,
-                does_loop: true,
+                steps,
+                cycle: Some(cycle),
             };
         }
-        visited.insert(tile_and_direction);
-        // try to go forward
-        let maybe_next_pos = match dir {
-            Direction::N => (pos.0, pos.1 - 1),
-            Direction::E => (pos.0 + 1, pos.1),
-            Direction::S => (pos.0, pos.1 + 1),
-            Direction::W => (pos.0 - 1, pos.1),
-        };
-        // if next pos is a wall instead turn 90 degrees instead
-        if info.wall_pos.contains(&maybe_next_pos) {
-            dir = match dir {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            };
-        } else {
-            // otherwise just go forward
-            pos = maybe_next_pos;
+        seen[pos] |= 1 << dir as u8;
+        steps.push((pos, dir));
+        match info.ahead(pos, dir) {
+            None => return VisitedInfo { steps, cycle: None },
+            Some(ahead) if info.obstacles[ahead] || Some(ahead) == obstacle => {
+                dir = dir.turn_clockwise();
+            }
+            Some(ahead) => pos = ahead,
         }
     }
-    VisitedInfo {
-        tiles: visited,
-        does_loop: false,
+}
+
+fn visited(info: &Info) -> VisitedInfo {
+    visited_with(info, None)
+}
+
+/// Describes and draws the loop the guard walks with an extra obstacle, noting whether walking
+/// tile by tile agrees with [`loops_with`]
+fn loop_witness(info: &Info, obstacle: Pos<usize>, jumps_loop: bool) -> String {
+    let walk = visited_with(info, Some(obstacle));
+    let summary = walk.cycle.as_ref().map_or_else(
+        || format!("obstacle at {:?}: no loop", (obstacle.x, obstacle.y)),
+        |cycle| {
+            format!(
+                "obstacle at {:?}: loop entered at {:?} facing {:?} after {} steps, {} steps long",
+                (obstacle.x, obstacle.y),
+                (cycle.entry.0.x, cycle.entry.0.y),
+                cycle.entry.1,
+                cycle.entry_step,
+                cycle.length
+            )
+        },
+    );
+    let disagreement = if walk.does_loop() == jumps_loop {
+        String::new()
+    } else {
+        format!("DISAGREEMENT: jumping between turns found a loop: {jumps_loop}\n")
+    };
+    format!(
+        "{summary}\n{disagreement}{}",
+        walk.render(info, Some(obstacle))
+    )
+}
+
+/// Whether the guard, about to walk from `pos` towards `dir`, ends up in a loop with an extra
+/// obstacle at `obstacle`
+///
+/// Only the tiles where the guard turns matter, so the walk jumps from one to the next.
+fn loops_with(info: &Info, obstacle: Pos<usize>, mut pos: Pos<usize>, mut dir: Direction) -> bool {
+    let mut seen = seen_directions(info);
+    while let Some(stop) = info.jump(pos, dir, obstacle) {
+        if seen[stop] & 1 << dir as u8 != 0 {
+            return true;
+        }
+        seen[stop] |= 1 << dir as u8;
+        pos = stop;
+        dir = dir.turn_clockwise();
     }
+    false
 }
 
-#[aoc_generator(day6)]
-fn parse(input: &str) -> Info {
-    let width = input
-        .lines()
-        .next()
-        .map(str::len)
-        .and_then(|n| n.try_into().ok())
+fn parse(input: &str, params: &Params) -> Result<Info, ParamsError> {
+    let grid = utils::parse_grid(input, |c| c);
+    let (x, y) = grid
+        .position(|c| matches!(c, '^' | '>' | 'v' | '<'))
         .unwrap();
-    let height = input.lines().count().try_into().unwrap();
-    let mut pos_iter = input
-        .chars()
-        .filter(char::is_ascii_graphic)
-        .enumerate()
-        .map(|(i, c)| {
-            let i = i32::try_from(i).unwrap();
-            ((i % width, i / width), c)
-        });
-    let wall_pos = pos_iter
-        .clone()
-        .filter(|(_, c)| *c == '#')
-        .map(|(pos, _)| pos)
-        .collect();
-    let (s_pos, s_dir) = pos_iter
-        .find_map(|((x, y), c)| match c {
-            '^' => Some(((x, y), Direction::N)),
-            '>' => Some(((x, y), Direction::E)),
-            'v' => Some(((x, y), Direction::S)),
-            '<' => Some(((x, y), Direction::W)),
-            _ => None,
-        })
-        .unwrap();
-    Info {
-        width,
-        height,
-        wall_pos,
-        s_pos,
-        s_dir,
-    }
+    let start_dir = Direction::try_from(grid[(x, y)]).unwrap();
+    let obstacles = Matrix::new(
+        grid.vec.iter().map(|&c| c == '#'),
+        grid.width(),
+        grid.height(),
+    );
+    Ok(Info {
+        jumps: jump_tables(&obstacles),
+        obstacles,
+        start: Pos::new(x, y),
+        start_dir,
+        show_loops: params.get_or("show_loops", false)?,
+    })
 }
 
-#[aoc(day6, part1)]
 fn part1(info: &Info) -> usize {
-    visited(info).unique_tiles().len()
+    visited(info).unique_tiles(info)
 }
 
-#[aoc(day6, part2)]
 fn part2(info: &Info) -> usize {
-    let normally_visited = visited(info).unique_tiles();
-    normally_visited
-        .into_iter()
-        .filter(|tile| {
-            let mut added_wall = info.wall_pos.clone();
-            added_wall.push(*tile);
-            let info = Info {
-                wall_pos: added_wall,
-                ..info.clone()
-            };
-            visited(&info).does_loop
-        })
-        .count()
+    // an obstacle only changes the walk from the first time the guard would bump into it, so
+    // each candidate resumes from the step just before that
+    let mut tried = Matrix::new_default(info.obstacles.width(), info.obstacles.height());
+    tried[info.start] = true;
+    let mut candidates = Vec::new();
+    for (pos, dir) in visited(info).steps {
+        if let Some(ahead) = info.ahead(pos, dir) {
+            if !info.obstacles[ahead] && !tried[ahead] {
+                tried[ahead] = true;
+                candidates.push((ahead, pos, dir));
+            }
+        }
+    }
+    let loops: Vec<_> = par_iter!(&candidates)
+        .map(|&(obstacle, pos, dir)| loops_with(info, obstacle, pos, dir))
+        .collect();
+    if info.show_loops {
+        // on stderr so that the answer stays alone on stdout, in the same order on every run
+        for (&(obstacle, ..), &jumps_loop) in candidates.iter().zip(&loops) {
+            if jumps_loop || visited_with(info, Some(obstacle)).does_loop() {
+                eprintln!("{}", loop_witness(info, obstacle, jumps_loop));
+            }
+        }
+    }
+    loops.into_iter().filter(|&loops| loops).count()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Info;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, params: &Params) -> Result<Self::Input, ParamsError> {
+        parse(input, params)
+    }
 
-    const EXAMPLE: &str = "....#.....
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "....#.....
 .........#
 ..........
 ..#.......
@@ -158,13 +298,70 @@ mod test {
 #.........
 ......#...";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test1() {
-        assert_eq!(part1(&parse(EXAMPLE)), 41);
+        assert_eq!(part1(&Day6::parse(EXAMPLE)), 41);
     }
 
     #[test]
     fn test2() {
-        assert_eq!(part2(&parse(EXAMPLE)), 6);
+        assert_eq!(part2(&Day6::parse(EXAMPLE)), 6);
+    }
+
+    #[test]
+    fn test_loop_witness() {
+        let info = Day6::parse(EXAMPLE);
+        let walk = visited_with(&info, Some(Pos::new(3, 6)));
+        assert_eq!(
+            walk.cycle,
+            Some(Cycle {
+                entry: (Pos::new(4, 6), Direction::N),
+                entry_step: 0,
+                length: 22
+            })
+        );
+        assert_eq!(
+            walk.render(&info, Some(Pos::new(3, 6))),
+            "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+"
+        );
+        assert!(!visited(&info).does_loop());
+    }
+
+    #[test]
+    fn test_jump_tables() {
+        let info = Day6::parse(EXAMPLE);
+        let jumps = |x, y, dir: Direction| info.jumps[Pos::new(x, y)][dir as usize];
+        assert_eq!(jumps(4, 6, Direction::N), Some(Pos::new(4, 1)));
+        assert_eq!(jumps(4, 1, Direction::E), Some(Pos::new(8, 1)));
+        assert_eq!(jumps(0, 0, Direction::W), None);
+        assert_eq!(jumps(1, 7, Direction::N), Some(Pos::new(1, 7)));
+        // an extra obstacle only matters when it comes before the first one
+        assert_eq!(
+            info.jump(Pos::new(4, 6), Direction::N, Pos::new(4, 3)),
+            Some(Pos::new(4, 4))
+        );
+        assert_eq!(
+            info.jump(Pos::new(4, 6), Direction::N, Pos::new(4, 0)),
+            Some(Pos::new(4, 1))
+        );
+        assert_eq!(
+            info.jump(Pos::new(0, 0), Direction::W, Pos::new(3, 0)),
+            None
+        );
     }
 }
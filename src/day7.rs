@@ -1,10 +1,17 @@
-use aoc_runner_derive::{aoc, aoc_generator};
-struct Equation {
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+    utils::par_iter,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
+
+pub struct Equation {
     target: u64,
     nums: Vec<u64>,
 }
 
-#[aoc_generator(day7)]
 fn parse(input: &str) -> Vec<Equation> {
     input
         .lines()
@@ -32,10 +39,8 @@ fn dfs(target: u64, current: u64, nums: &[u64]) -> bool {
     dfs(target, current + nums[0], &nums[1..]) || dfs(target, current * nums[0], &nums[1..])
 }
 
-#[aoc(day7, part1)]
 fn part1(input: &[Equation]) -> u64 {
-    input
-        .iter()
+    par_iter!(input)
         .filter(|eq| dfs(eq.target, 0, &eq.nums))
         .map(|eq| eq.target)
         .sum()
@@ -57,20 +62,34 @@ fn dfs2(target: u64, current: u64, nums: &[u64]) -> bool {
         || dfs2(target, concat(current, nums[0]), &nums[1..])
 }
 
-#[aoc(day7, part2)]
 fn part2(input: &[Equation]) -> u64 {
-    input
-        .iter()
+    par_iter!(input)
         .filter(|eq| dfs2(eq.target, 0, &eq.nums))
         .map(|eq| eq.target)
         .sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Equation>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
 
-    const EXAMPLE: &str = "190: 10 19
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -81,6 +100,10 @@ mod tests {
 292: 11 6 16 20
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_concat() {
         assert_eq!(concat(123, 45), 12345);
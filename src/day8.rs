@@ -1,92 +1,101 @@
-use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashMap, HashSet};
 
-type Antennas = HashMap<char, Vec<(i64, i64)>>;
+use crate::{
+    params::{Params, ParamsError},
+    pos::Pos,
+    solution::Solution,
+    sparse_grid::SparseGrid,
+    utils,
+};
 
 #[derive(Debug)]
-struct Infos {
+pub struct Infos {
     width: i64,
     height: i64,
-    antennas: Antennas,
+    antennas: SparseGrid<char>,
 }
 
-#[aoc_generator(day8)]
-fn parse(input: &str) -> Infos {
-    let width = input.lines().next().unwrap().trim().len();
-    let height = input.lines().count();
-    let mut antennas: Antennas = HashMap::new();
-    input
-        .chars()
-        .filter(char::is_ascii_graphic)
-        .enumerate()
-        .map(|(i, c)| ((i % width, i / width), c))
-        .filter(|(_, c)| *c != '.')
-        .for_each(|((x, y), c)| {
-            antennas
-                .entry(c)
-                .or_default()
-                .push((x.try_into().unwrap(), y.try_into().unwrap()));
-        });
+impl Infos {
+    fn in_bounds(&self, pos: Pos<i64>) -> bool {
+        (0..self.width).contains(&pos.x) && (0..self.height).contains(&pos.y)
+    }
+
+    /// The positions of the antennas, grouped by frequency
+    fn frequencies(&self) -> HashMap<char, Vec<Pos<i64>>> {
+        let mut frequencies: HashMap<_, Vec<_>> = HashMap::new();
+        for (&pos, &c) in &self.antennas {
+            frequencies.entry(c).or_default().push(pos);
+        }
+        frequencies
+    }
+}
 
+fn parse(input: &str) -> Infos {
+    let grid = utils::parse_grid(input, |c| c);
     Infos {
-        width: width.try_into().unwrap(),
-        height: height.try_into().unwrap(),
-        antennas,
+        width: grid.width().try_into().unwrap(),
+        height: grid.height().try_into().unwrap(),
+        antennas: SparseGrid::from_matrix(grid, Pos::new(0, 0), |c| *c != '.'),
     }
 }
 
-#[aoc(day8, part1)]
 fn part1(input: &Infos) -> usize {
     let mut antinodes = HashSet::new();
-    for antenna_pos in input.antennas.values() {
-        for (i, a) in antenna_pos[..antenna_pos.len() - 1].iter().enumerate() {
-            for b in &antenna_pos[i + 1..] {
-                let diff = (b.0 - a.0, b.1 - a.1);
-                let anti1 = (b.0 + diff.0, b.1 + diff.1);
-                let anti2 = (a.0 - diff.0, a.1 - diff.1);
-                antinodes.insert(anti1);
-                antinodes.insert(anti2);
+    for antenna_pos in input.frequencies().values() {
+        for (i, &a) in antenna_pos[..antenna_pos.len() - 1].iter().enumerate() {
+            for &b in &antenna_pos[i + 1..] {
+                let diff = b - a;
+                antinodes.insert(b + diff);
+                antinodes.insert(a - diff);
             }
         }
     }
     antinodes
-        .iter()
-        .filter(|(x, y)| (0..input.width).contains(x) && (0..input.height).contains(y))
+        .into_iter()
+        .filter(|antinode| input.in_bounds(*antinode))
         .count()
 }
 
-#[aoc(day8, part2)]
-#[allow(clippy::maybe_infinite_iter)] // it's OK
 fn part2(input: &Infos) -> usize {
     let mut antinodes = HashSet::new();
-    let in_bounds = |(x, y)| (0..input.width).contains(&x) && (0..input.height).contains(&y);
-    for antenna_pos in input.antennas.values() {
-        for (i, a) in antenna_pos[..antenna_pos.len() - 1].iter().enumerate() {
-            for b in &antenna_pos[i + 1..] {
-                let diff = (b.0 - a.0, b.1 - a.1);
-                (0..)
-                    .map(|lambda| (b.0 + lambda * diff.0, b.1 + lambda * diff.1))
-                    .take_while(|antinode| in_bounds(*antinode))
-                    .for_each(|antinode| {
-                        antinodes.insert(antinode);
-                    });
-                (0..)
-                    .map(|lambda| (a.0 - lambda * diff.0, a.1 - lambda * diff.1))
-                    .take_while(|antinode| in_bounds(*antinode))
-                    .for_each(|antinode| {
-                        antinodes.insert(antinode);
-                    });
+    for antenna_pos in input.frequencies().values() {
+        for (i, &a) in antenna_pos[..antenna_pos.len() - 1].iter().enumerate() {
+            for &b in &antenna_pos[i + 1..] {
+                // every lattice point on the line, not only those a multiple of `b - a` away
+                let step = (b - a).reduced();
+                for step in [step, -step] {
+                    antinodes.extend(
+                        std::iter::successors(Some(a), |p| Some(*p + step))
+                            .take_while(|antinode| input.in_bounds(*antinode)),
+                    );
+                }
             }
         }
     }
     antinodes.len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Infos;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    const EXAMPLE: &str = "............
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok(parse(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(input))
+    }
+}
+
+pub const EXAMPLE: &str = "............
 ........0...
 .....0......
 .......0....
@@ -100,6 +109,10 @@ mod tests {
 ............
 ";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part1_example() {
         assert_eq!(part1(&parse(EXAMPLE)), 14);
@@ -1,10 +1,12 @@
 use std::fmt::Debug;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::{
+    params::{Params, ParamsError},
+    solution::Solution,
+};
 
-type Memory = Vec<Option<usize>>;
+pub type Memory = Vec<Option<usize>>;
 
-#[aoc_generator(day9, part1)]
 fn parse1(input: &str) -> Memory {
     input
         .chars()
@@ -31,7 +33,6 @@ fn checksum(input: Memory) -> usize {
         .sum()
 }
 
-#[aoc(day9, part1)]
 fn part1(input: &Memory) -> usize {
     let mut input = input.to_owned();
     loop {
@@ -45,7 +46,7 @@ fn part1(input: &Memory) -> usize {
 }
 
 #[derive(Clone, Debug)]
-struct MemoryShard {
+pub struct MemoryShard {
     id: Option<usize>,
     size: usize,
 }
@@ -56,7 +57,6 @@ impl MemoryShard {
     }
 }
 
-#[aoc_generator(day9, part2)]
 fn parse2(input: &str) -> Vec<MemoryShard> {
     input
         .chars()
@@ -70,7 +70,6 @@ fn parse2(input: &str) -> Vec<MemoryShard> {
         .collect()
 }
 
-#[aoc(day9, part2)]
 fn part2(input: &[MemoryShard]) -> usize {
     let mut input = input.to_owned();
     let max_id = input.iter().filter_map(|shard| shard.id).max().unwrap();
@@ -94,12 +93,32 @@ fn part2(input: &[MemoryShard]) -> usize {
     checksum(input.iter().flat_map(MemoryShard::raw_memory).collect())
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = (Memory, Vec<MemoryShard>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse_body(input: &str, _params: &Params) -> Result<Self::Input, ParamsError> {
+        Ok((parse1(input), parse2(input)))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        part1(&input.0)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Part2> {
+        Some(part2(&input.1))
+    }
+}
+
+pub const EXAMPLE: &str = "2333133121414131402";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = "2333133121414131402";
-
     #[test]
     fn part1_example() {
         assert_eq!(part1(&parse1(EXAMPLE)), 1928);
@@ -0,0 +1,77 @@
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day23,
+    day3, day4, day5, day6, day7, day8, day9,
+};
+
+/// A puzzle example along with its expected answer
+pub struct Example {
+    pub day: u32,
+    pub part: u32,
+    pub input: &'static str,
+    pub expected: &'static str,
+}
+
+macro_rules! examples {
+    ($($day:literal, $part:literal, $input:expr => $expected:literal;)*) => {
+        &[$(Example {
+            day: $day,
+            part: $part,
+            input: $input,
+            expected: $expected,
+        }),*]
+    };
+}
+
+/// The examples the solutions can run on, their `@` header setting the parameters that differ
+/// from the real inputs, ordered by day then part
+pub const EXAMPLES: &[Example] = examples! {
+    1, 1, day1::EXAMPLE => "11";
+    1, 2, day1::EXAMPLE => "31";
+    2, 1, day2::EXAMPLE => "2";
+    2, 2, day2::EXAMPLE => "4";
+    3, 1, day3::EXAMPLE1 => "161";
+    3, 2, day3::EXAMPLE2 => "48";
+    4, 1, day4::EXAMPLE => "18";
+    4, 2, day4::EXAMPLE => "9";
+    5, 1, day5::EXAMPLE => "143";
+    5, 2, day5::EXAMPLE => "123";
+    6, 1, day6::EXAMPLE => "41";
+    6, 2, day6::EXAMPLE => "6";
+    7, 1, day7::EXAMPLE => "3749";
+    7, 2, day7::EXAMPLE => "11387";
+    8, 1, day8::EXAMPLE => "14";
+    8, 2, day8::EXAMPLE => "34";
+    9, 1, day9::EXAMPLE => "1928";
+    9, 2, day9::EXAMPLE => "2858";
+    10, 1, day10::EXAMPLE1 => "36";
+    10, 1, day10::EXAMPLE2 => "2";
+    10, 1, day10::EXAMPLE3 => "4";
+    10, 1, day10::EXAMPLE4 => "3";
+    10, 2, day10::EXAMPLE1 => "81";
+    11, 1, day11::EXAMPLE => "55312";
+    12, 1, day12::EXAMPLE1 => "140";
+    12, 1, day12::EXAMPLE2 => "772";
+    12, 1, day12::EXAMPLE3 => "1930";
+    12, 2, day12::EXAMPLE1 => "80";
+    12, 2, day12::EXAMPLE2 => "436";
+    12, 2, day12::EXAMPLE3 => "1206";
+    13, 1, day13::EXAMPLE => "480";
+    13, 2, day13::EXAMPLE => "875318608908";
+    14, 1, day14::EXAMPLE => "12";
+    15, 1, day15::EXAMPLE1 => "2028";
+    15, 1, day15::EXAMPLE2 => "10092";
+    15, 2, day15::EXAMPLE2 => "9021";
+    16, 1, day16::EXAMPLE => "7036";
+    16, 1, day16::EXAMPLE2 => "11048";
+    16, 2, day16::EXAMPLE => "45";
+    16, 2, day16::EXAMPLE2 => "64";
+    17, 1, day17::EXAMPLE => "4,6,3,5,6,3,5,2,1,0";
+    17, 2, day17::EXAMPLE2 => "117440";
+    18, 1, day18::EXAMPLE => "22";
+    18, 2, day18::EXAMPLE => "6,1";
+    19, 1, day19::EXAMPLE => "6";
+    19, 2, day19::EXAMPLE => "16";
+    20, 1, day20::EXAMPLE => "1";
+    23, 1, day23::EXAMPLE => "7";
+    23, 2, day23::EXAMPLE => "co,de,ka,ta";
+};
@@ -1,5 +1,6 @@
 #![feature(iter_map_windows)]
 
+pub mod computer;
 mod day1;
 mod day10;
 mod day11;
@@ -21,8 +22,18 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+pub mod examples;
 pub mod matrix;
+pub mod params;
 pub mod pos;
+pub mod search;
+pub mod solution;
+pub mod sparse_grid;
 pub mod utils;
+pub mod verify;
 
-aoc_runner_derive::aoc_lib! { year = 2024 }
+pub use crate::{
+    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
+    day16::Day16, day17::Day17, day18::Day18, day19::Day19, day2::Day2, day20::Day20, day23::Day23,
+    day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9,
+};
@@ -0,0 +1,301 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process::ExitCode,
+    time::Instant,
+};
+
+use advent_2024::{
+    examples::EXAMPLES,
+    params::{Params, PARAMS_VAR},
+    solution::{self, Solver, REGISTRY, YEAR},
+    verify::{self, Answers, Verdict},
+};
+
+const USAGE: &str = "\
+Usage: advent-2024 --day N [--part P] [--input PATH] [--param KEY=VALUE]...
+       advent-2024 --all [--part P] [--input-dir DIR] [--param KEY=VALUE]...
+       advent-2024 --example [--day N] [--part P] [--param KEY=VALUE]...
+       advent-2024 --verify [--day N] [--part P] [--input-dir DIR] [--answers PATH]
+
+Options:
+  --day N          Day to run
+  --part P         Part to run, both when omitted
+  --input PATH     Input of the day, read from stdin when omitted or `-`
+  --all            Run every registered day, reading DIR/dayN.txt
+  --input-dir DIR  Where --all and --verify read inputs from [default: input/2024]
+  --example        Run the embedded examples and check their answers
+  --verify         Check the answers on the inputs against the recorded ones
+  --answers PATH   Recorded answers for --verify [default: answers/2024.toml]
+  --param K=V      Set a puzzle parameter, such as width=11, overriding the `@` header of
+                   the inputs and the AOC_PARAMS environment variable
+  -h, --help       Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    All,
+    Example,
+    Verify,
+    Help,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+    input_dir: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    /// Overrides of the input parameters
+    params: Params,
+    /// What to do instead of running `day`
+    mode: Option<Mode>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--day" => parsed.day = Some(value()?.parse().map_err(|e| format!("--day: {e}"))?),
+            "--part" => {
+                let part = value()?.parse().map_err(|e| format!("--part: {e}"))?;
+                if !matches!(part, 1 | 2) {
+                    return Err(format!("--part: no part {part}, expected 1 or 2"));
+                }
+                parsed.part = Some(part);
+            }
+            "--input" => parsed.input = Some(value()?.into()),
+            "--input-dir" => parsed.input_dir = Some(value()?.into()),
+            "--answers" => parsed.answers = Some(value()?.into()),
+            "--param" => {
+                let params = Params::parse(&value()?).map_err(|e| format!("--param: {e}"))?;
+                parsed.params.extend(&params);
+            }
+            "--all" => parsed.mode = Some(Mode::All),
+            "--example" => parsed.mode = Some(Mode::Example),
+            "--verify" => parsed.mode = Some(Mode::Verify),
+            "-h" | "--help" => parsed.mode = Some(Mode::Help),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+    if parsed.mode.is_none() && parsed.day.is_none() {
+        return Err("one of --day, --all, --example or --verify is required".to_string());
+    }
+    if parsed.mode == Some(Mode::All) && parsed.day.is_some() {
+        return Err("--all and --day cannot be used together".to_string());
+    }
+    Ok(parsed)
+}
+
+fn read_input(path: Option<&PathBuf>) -> io::Result<String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => fs::read_to_string(path),
+        _ => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn selected(args: &Args) -> impl Iterator<Item = &'static Solver> + '_ {
+    REGISTRY
+        .iter()
+        .filter(|s| s.year == YEAR && args.day.is_none_or(|day| s.day == day))
+}
+
+fn parts(args: &Args) -> impl Iterator<Item = u32> {
+    let selected = args.part;
+    (1..=2).filter(move |part| selected.is_none_or(|p| p == *part))
+}
+
+/// Parses the input and solves the selected parts, printing answers and timings
+fn report(args: &Args, solver: &Solver, input: &str) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    let parsed = solver.parse_with(input, &args.params)?;
+    println!("Day {} - parse: {:?}", solver.day, start.elapsed());
+    for part in parts(args) {
+        let start = Instant::now();
+        if let Some(answer) = solver.solve(part, &parsed) {
+            println!(
+                "Day {} - Part {part}: {answer} ({:?})",
+                solver.day,
+                start.elapsed()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_day(args: &Args) -> Result<(), Box<dyn Error>> {
+    let day = args.day.unwrap_or_default();
+    let solver =
+        solution::find(YEAR, day).ok_or_else(|| format!("no solution registered for day {day}"))?;
+    let input = read_input(args.input.as_ref())?;
+    report(args, solver, &input)
+}
+
+fn input_path(args: &Args, day: u32) -> PathBuf {
+    args.input_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("input/2024"))
+        .join(format!("day{day}.txt"))
+}
+
+fn run_all(args: &Args) -> bool {
+    let mut ok = true;
+    for solver in selected(args) {
+        let path = input_path(args, solver.day);
+        match fs::read_to_string(&path) {
+            Ok(input) => {
+                if let Err(err) = report(args, solver, &input) {
+                    println!("Day {}: FAILED: {err}", solver.day);
+                    ok = false;
+                }
+            }
+            Err(err) => {
+                println!(
+                    "Day {}: skipped, cannot read {}: {err}",
+                    solver.day,
+                    path.display()
+                );
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+fn run_examples(args: &Args) -> bool {
+    let mut failures = 0;
+    let mut count = 0;
+    let examples = EXAMPLES.iter().filter(|e| {
+        args.day.is_none_or(|day| e.day == day) && args.part.is_none_or(|part| e.part == part)
+    });
+    for example in examples {
+        count += 1;
+        let name = format!("Day {} - Part {} example", example.day, example.part);
+        let Some(solver) = solution::find(YEAR, example.day) else {
+            println!("{name}: FAILED: no solution registered");
+            failures += 1;
+            continue;
+        };
+        let start = Instant::now();
+        let parsed = match solver.parse_with(example.input, &args.params) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                println!("{name}: FAILED: {err}");
+                failures += 1;
+                continue;
+            }
+        };
+        let answer = solver.solve(example.part, &parsed).unwrap_or_default();
+        if answer == example.expected {
+            println!("{name}: ok {answer} ({:?})", start.elapsed());
+        } else {
+            println!(
+                "{name}: FAILED: expected {}, got {answer}",
+                example.expected
+            );
+            failures += 1;
+        }
+    }
+    println!("{} of {count} examples passed", count - failures);
+    failures == 0
+}
+
+fn run_verify(args: &Args) -> Result<bool, Box<dyn Error>> {
+    let answers_path = args
+        .answers
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("answers/2024.toml"));
+    let answers =
+        Answers::load(&answers_path).map_err(|err| format!("{}: {err}", answers_path.display()))?;
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solver in selected(args) {
+        let input = fs::read_to_string(input_path(args, solver.day)).ok();
+        let parsed = input
+            .map(|input| solver.parse_with(&input, &args.params))
+            .transpose()
+            .map_err(|err| format!("day {}: {err}", solver.day))?;
+        for part in parts(args) {
+            let name = format!("Day {} - Part {part}", solver.day);
+            let verdict = match &parsed {
+                Some(parsed) => match solver.solve(part, parsed) {
+                    Some(actual) => Verdict::check(answers.get(solver.day, part), actual),
+                    // not solved yet
+                    None => continue,
+                },
+                None => Verdict::MissingInput,
+            };
+            match verdict {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("{name}: pass");
+                }
+                Verdict::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("{name}: FAIL\n{}", verify::diff(&expected, &actual));
+                }
+                Verdict::MissingAnswer { actual } => {
+                    missing += 1;
+                    println!("{name}: missing answer, got {actual}");
+                }
+                Verdict::MissingInput => {
+                    missing += 1;
+                    println!("{name}: missing input");
+                }
+            }
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    Ok(failed == 0)
+}
+
+fn main() -> ExitCode {
+    let mut args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // flags take precedence over the environment
+    match Params::from_env() {
+        Ok(mut params) => {
+            params.extend(&args.params);
+            args.params = params;
+        }
+        Err(err) => {
+            eprintln!("error: {PARAMS_VAR}: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let ok = match args.mode {
+        Some(Mode::Help) => {
+            println!("{USAGE}");
+            true
+        }
+        Some(Mode::All) => run_all(&args),
+        Some(Mode::Example) => run_examples(&args),
+        Some(Mode::Verify) => run_verify(&args).unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            false
+        }),
+        None => run_day(&args)
+            .inspect_err(|err| eprintln!("error: {err}"))
+            .is_ok(),
+    };
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,7 +1,16 @@
 use std::fmt;
 use std::ops;
 
-use crate::pos::Pos;
+use crate::pos::{Compass, Pos};
+
+mod components;
+mod torus;
+mod transform;
+mod view;
+
+pub use components::{Component, Components};
+pub use torus::Torus;
+pub use view::{MatrixView, MatrixViewMut};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Matrix<T> {
@@ -33,7 +42,7 @@ impl<T> Matrix<T> {
         };
         if x >= self.width || y >= self.height {
             return None;
-        };
+        }
         self.vec.get(x + y * self.width)
     }
 
@@ -44,7 +53,7 @@ impl<T> Matrix<T> {
         };
         if x >= self.width || y >= self.height {
             return None;
-        };
+        }
         self.vec.get_mut(x + y * self.width)
     }
 
@@ -97,6 +106,17 @@ impl<T> Matrix<T> {
         result
     }
 
+    /// The in-bounds neighbours of `pos` in every direction of `D`, along with the direction taken
+    pub fn neighbors<D: Compass>(&self, pos: Pos<usize>) -> impl Iterator<Item = (D, Pos<usize>)> {
+        let (width, height) = (self.width, self.height);
+        D::ALL.iter().filter_map(move |&d| {
+            let delta = d.delta();
+            let x = pos.x.checked_add_signed(delta.x).filter(|&x| x < width)?;
+            let y = pos.y.checked_add_signed(delta.y).filter(|&y| y < height)?;
+            Some((d, Pos::new(x, y)))
+        })
+    }
+
     #[must_use]
     pub const fn width(&self) -> usize {
         self.width
@@ -113,14 +133,14 @@ impl<T> Matrix<T> {
     }
 
     #[must_use]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.vec.is_empty()
     }
 
     /// # Panics
     /// if the other's width is not equal to self's width
     pub fn insert_row(&mut self, mut other: Self, at: usize) {
-        assert!(other.width() == self.width());
+        assert_eq!(other.width(), self.width());
         let idx = at * self.width();
         self.height += other.height;
         let mut after = self.vec.split_off(idx);
@@ -312,6 +332,7 @@ impl<T> ops::IndexMut<Pos<usize>> for Matrix<T> {
 #[cfg(test)]
 mod test_matrix {
     use super::*;
+    use crate::pos::{Direction, Direction8};
     #[test]
     fn test_swap() {
         let items = 0..6;
@@ -352,4 +373,18 @@ mod test_matrix {
         );
         assert_eq!(None, iter_pos.next());
     }
+
+    #[test]
+    fn test_neighbors() {
+        let matrix = Matrix::new(0..6, 3, 2);
+        let neighbors: Vec<_> = matrix.neighbors(Pos::new(0, 0)).collect();
+        assert_eq!(
+            neighbors,
+            [
+                (Direction::E, Pos::new(1, 0)),
+                (Direction::S, Pos::new(0, 1))
+            ]
+        );
+        assert_eq!(matrix.neighbors::<Direction8>(Pos::new(1, 1)).count(), 5);
+    }
 }
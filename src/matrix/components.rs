@@ -0,0 +1,132 @@
+use crate::pos::{Compass, Direction, Pos};
+
+use super::Matrix;
+
+/// A set of cells connected to each other, as found by [`Matrix::connected_components`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// Number of cells
+    pub area: usize,
+    /// Number of cell edges between the component and the outside
+    pub perimeter: usize,
+    /// Number of straight fences the perimeter is made of
+    pub sides: usize,
+    /// Smallest and largest corners of the bounding box, both included
+    pub bbox: (Pos<usize>, Pos<usize>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// The index in `components` of the component of each cell
+    pub labels: Matrix<usize>,
+    pub components: Vec<Component>,
+}
+
+impl<T> Matrix<T> {
+    /// Labels the groups of cells that are connected through neighbours in `D` for which `eq`
+    /// holds, in row-major order of their first cell
+    pub fn connected_components<D, F>(&self, eq: F) -> Components
+    where
+        D: Compass,
+        F: Fn(&T, &T) -> bool,
+    {
+        const UNSET: usize = usize::MAX;
+        let mut labels = Matrix::new_with(self.width, self.height, || UNSET);
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+        for start in 0..self.vec.len() {
+            if labels.vec[start] != UNSET {
+                continue;
+            }
+            let label = components.len();
+            let start = Pos::new(start % self.width, start / self.width);
+            let mut bbox = (start, start);
+            let mut area = 0;
+            labels[start] = label;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                area += 1;
+                bbox = (bbox.0.min(pos), bbox.1.max(pos));
+                for (_, n) in self.neighbors::<D>(pos) {
+                    if labels[n] == UNSET && eq(&self[pos], &self[n]) {
+                        labels[n] = label;
+                        stack.push(n);
+                    }
+                }
+            }
+            components.push(Component {
+                area,
+                perimeter: 0,
+                sides: 0,
+                bbox,
+            });
+        }
+
+        // an edge of a cell is on the perimeter when the cell across it is in another component
+        let is_fence = |pos: Pos<usize>, d: Direction| {
+            pos.checked_add_signed(d.delta())
+                .filter(|n| n.x < self.width && n.y < self.height)
+                .is_none_or(|n| labels[n] != labels[pos])
+        };
+        for (i, &label) in labels.vec.iter().enumerate() {
+            let pos = Pos::new(i % self.width, i / self.width);
+            let component = &mut components[label];
+            for d in Direction::ALL {
+                if !is_fence(pos, d) {
+                    continue;
+                }
+                component.perimeter += 1;
+                // only count a side from the cell at its counter-clockwise end
+                let previous = pos
+                    .checked_add_signed(d.turn_counter_clockwise().delta())
+                    .filter(|p| p.x < self.width && p.y < self.height);
+                if previous.is_none_or(|p| labels[p] != label || !is_fence(p, d)) {
+                    component.sides += 1;
+                }
+            }
+        }
+
+        Components { labels, components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pos::Direction8, utils};
+
+    #[test]
+    fn test_components() {
+        let grid = utils::parse_grid("AAB\nABB\nCCB", |c| c);
+        let Components { labels, components } =
+            grid.connected_components::<Direction, _>(|a, b| a == b);
+        assert_eq!(labels.vec, [0, 0, 1, 0, 1, 1, 2, 2, 1]);
+        assert_eq!(
+            components[1],
+            Component {
+                area: 4,
+                perimeter: 10,
+                sides: 8,
+                bbox: (Pos::new(1, 0), Pos::new(2, 2)),
+            }
+        );
+        assert_eq!(components[2].sides, 4);
+    }
+
+    #[test]
+    fn test_diagonal_connectivity() {
+        let grid = utils::parse_grid("#.\n.#", |c| c == '#');
+        let four = grid.connected_components::<Direction, _>(|a, b| a == b);
+        let eight = grid.connected_components::<Direction8, _>(|a, b| a == b);
+        assert_eq!(four.components.len(), 4);
+        assert_eq!(eight.components.len(), 2);
+    }
+
+    #[test]
+    fn test_large_region_does_not_overflow() {
+        let grid = Matrix::new(std::iter::repeat_n('A', 1000 * 1000), 1000, 1000);
+        let components = grid.connected_components::<Direction, _>(|a, b| a == b);
+        assert_eq!(components.components.len(), 1);
+        assert_eq!(components.components[0].sides, 4);
+    }
+}
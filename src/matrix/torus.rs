@@ -0,0 +1,164 @@
+use std::{fmt, ops};
+
+use crate::pos::{Compass, Pos};
+
+use super::Matrix;
+
+/// A [`Matrix`] whose edges wrap around: leaving through one side enters through the opposite one
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Torus<T> {
+    matrix: Matrix<T>,
+}
+
+impl<T> Torus<T> {
+    #[must_use]
+    pub const fn new(matrix: Matrix<T>) -> Self {
+        Self { matrix }
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Matrix<T> {
+        self.matrix
+    }
+
+    #[must_use]
+    pub const fn as_matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    pub const fn as_matrix_mut(&mut self) -> &mut Matrix<T> {
+        &mut self.matrix
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.matrix.width()
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.matrix.height()
+    }
+
+    /// The position `pos` designates once wrapped around the edges
+    ///
+    /// # Panics
+    /// if the torus is empty
+    #[must_use]
+    pub fn wrap(&self, pos: Pos<isize>) -> Pos<usize> {
+        let width = isize::try_from(self.width()).unwrap();
+        let height = isize::try_from(self.height()).unwrap();
+        Pos::new(pos.x.rem_euclid(width), pos.y.rem_euclid(height)).to_usize()
+    }
+
+    /// `pos + delta`, wrapped around the edges
+    ///
+    /// # Panics
+    /// if the torus is empty
+    #[must_use]
+    pub fn offset(&self, pos: Pos<usize>, delta: Pos<isize>) -> Pos<usize> {
+        let pos = Pos::new(pos.x % self.width(), pos.y % self.height());
+        self.wrap(pos.to_isize() + delta)
+    }
+
+    /// # Panics
+    /// if the torus is empty
+    #[must_use]
+    pub fn get(&self, x: isize, y: isize) -> &T {
+        &self.matrix[self.wrap(Pos::new(x, y))]
+    }
+
+    /// # Panics
+    /// if the torus is empty
+    pub fn get_mut(&mut self, x: isize, y: isize) -> &mut T {
+        let pos = self.wrap(Pos::new(x, y));
+        &mut self.matrix[pos]
+    }
+
+    /// The neighbours of `pos` in every direction of `D`, wrapping around the edges
+    pub fn neighbors<D: Compass>(
+        &self,
+        pos: Pos<usize>,
+    ) -> impl Iterator<Item = (D, Pos<usize>)> + '_ {
+        D::ALL
+            .iter()
+            .map(move |&d| (d, self.offset(pos, d.delta())))
+    }
+}
+
+impl<T> From<Matrix<T>> for Torus<T> {
+    fn from(matrix: Matrix<T>) -> Self {
+        Self::new(matrix)
+    }
+}
+
+impl<T> ops::Index<Pos<isize>> for Torus<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<isize>) -> &Self::Output {
+        self.get(pos.x, pos.y)
+    }
+}
+
+impl<T> ops::IndexMut<Pos<isize>> for Torus<T> {
+    fn index_mut(&mut self, pos: Pos<isize>) -> &mut Self::Output {
+        self.get_mut(pos.x, pos.y)
+    }
+}
+
+impl<T> ops::Index<Pos<usize>> for Torus<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<usize>) -> &Self::Output {
+        &self.matrix[Pos::new(pos.x % self.width(), pos.y % self.height())]
+    }
+}
+
+impl<T> ops::IndexMut<Pos<usize>> for Torus<T> {
+    fn index_mut(&mut self, pos: Pos<usize>) -> &mut Self::Output {
+        let pos = Pos::new(pos.x % self.width(), pos.y % self.height());
+        &mut self.matrix[pos]
+    }
+}
+
+impl<T> fmt::Display for Torus<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.matrix.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pos::Direction;
+
+    #[test]
+    fn test_wrapping_access() {
+        let torus = Torus::new(Matrix::new(0..6, 3, 2));
+        assert_eq!(*torus.get(-1, 0), 2);
+        assert_eq!(*torus.get(3, 3), 3);
+        assert_eq!(torus[Pos::new(-4_isize, -1)], 5);
+        assert_eq!(
+            torus.offset(Pos::new(2, 1), Pos::new(7, -3)),
+            Pos::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn test_neighbors_wrap() {
+        let torus = Torus::new(Matrix::new(0..6, 3, 2));
+        let neighbors: Vec<_> = torus.neighbors(Pos::new(0, 0)).collect();
+        assert_eq!(
+            neighbors,
+            [
+                (Direction::N, Pos::new(0, 1)),
+                (Direction::E, Pos::new(1, 0)),
+                (Direction::S, Pos::new(0, 1)),
+                (Direction::W, Pos::new(2, 0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use super::Matrix;
+
+impl<T> Matrix<T> {
+    /// Builds a `width` by `height` matrix whose item at `(x, y)` is moved out of `self` at
+    /// `source(x, y)`, which must be a bijection
+    fn permuted<F>(self, width: usize, height: usize, source: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let old_width = self.width;
+        let mut slots: Vec<_> = self.vec.into_iter().map(Some).collect();
+        let vec = (0..width * height)
+            .map(|i| {
+                let (x, y) = source(i % width, i / width);
+                slots[x + y * old_width]
+                    .take()
+                    .expect("source should be a bijection")
+            })
+            .collect();
+        Self { vec, width, height }
+    }
+
+    #[must_use]
+    pub fn transpose(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.permuted(width, height, |x, y| (y, x))
+    }
+
+    /// Rotates by a quarter turn clockwise
+    #[must_use]
+    pub fn rotate_cw(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.permuted(width, height, |x, y| (y, width - 1 - x))
+    }
+
+    /// Rotates by a quarter turn counter-clockwise
+    #[must_use]
+    pub fn rotate_ccw(self) -> Self {
+        let (width, height) = (self.height, self.width);
+        self.permuted(width, height, |x, y| (height - 1 - y, x))
+    }
+
+    /// Mirrors left and right
+    #[must_use]
+    pub fn flip_horizontal(mut self) -> Self {
+        self.flip_horizontal_in_place();
+        self
+    }
+
+    /// Mirrors top and bottom
+    #[must_use]
+    pub fn flip_vertical(mut self) -> Self {
+        self.flip_vertical_in_place();
+        self
+    }
+
+    pub fn flip_horizontal_in_place(&mut self) {
+        for y in 0..self.height {
+            self.row_mut(y).reverse();
+        }
+    }
+
+    pub fn flip_vertical_in_place(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.vec.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    /// # Panics
+    /// if the matrix is not square
+    pub fn transpose_in_place(&mut self) {
+        assert_eq!(
+            self.width, self.height,
+            "in place transposition needs a square matrix"
+        );
+        for y in 0..self.height {
+            for x in y + 1..self.width {
+                self.swap((x, y), (y, x));
+            }
+        }
+    }
+
+    /// # Panics
+    /// if the matrix is not square
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_horizontal_in_place();
+    }
+
+    /// # Panics
+    /// if the matrix is not square
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_vertical_in_place();
+    }
+
+    /// The four rotations of the matrix, starting with itself and turning clockwise
+    pub fn rotations(&self) -> impl Iterator<Item = Self>
+    where
+        T: Clone,
+    {
+        std::iter::successors(Some(self.clone()), |m| Some(m.clone().rotate_cw())).take(4)
+    }
+
+    /// The eight symmetries of the square: the four [rotations](Self::rotations), then the four
+    /// rotations of the horizontally flipped matrix
+    pub fn symmetries(&self) -> impl Iterator<Item = Self>
+    where
+        T: Clone,
+    {
+        let flipped = self.clone().flip_horizontal();
+        let flipped_rotations: Vec<_> = flipped.rotations().collect();
+        self.rotations().chain(flipped_rotations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 1 2
+    // 3 4 5
+    fn sample() -> Matrix<i32> {
+        Matrix::new(0..6, 3, 2)
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = sample().transpose();
+        assert_eq!((m.width(), m.height()), (2, 3));
+        assert_eq!(m.vec, [0, 3, 1, 4, 2, 5]);
+    }
+
+    #[test]
+    fn test_rotations() {
+        assert_eq!(sample().rotate_cw().vec, [3, 0, 4, 1, 5, 2]);
+        assert_eq!(sample().rotate_ccw().vec, [2, 5, 1, 4, 0, 3]);
+        assert_eq!(sample().rotate_cw().rotate_ccw(), sample());
+    }
+
+    #[test]
+    fn test_flips() {
+        assert_eq!(sample().flip_horizontal().vec, [2, 1, 0, 5, 4, 3]);
+        assert_eq!(sample().flip_vertical().vec, [3, 4, 5, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_in_place_matches_owning() {
+        let square = Matrix::new(0..9, 3, 3);
+        let mut m = square.clone();
+        m.rotate_cw_in_place();
+        assert_eq!(m, square.clone().rotate_cw());
+        m = square.clone();
+        m.rotate_ccw_in_place();
+        assert_eq!(m, square.clone().rotate_ccw());
+        m = square.clone();
+        m.transpose_in_place();
+        assert_eq!(m, square.transpose());
+    }
+
+    #[test]
+    fn test_symmetries_are_distinct() {
+        let square = Matrix::new(0..9, 3, 3);
+        let mut symmetries: Vec<_> = square.symmetries().map(|m| m.vec).collect();
+        symmetries.sort();
+        symmetries.dedup();
+        assert_eq!(symmetries.len(), 8);
+    }
+}
@@ -0,0 +1,358 @@
+use std::ops;
+
+use crate::pos::Pos;
+
+use super::Matrix;
+
+/// A borrowed rectangular window into a [`Matrix`], indexed relative to its origin
+pub struct MatrixView<'a, T> {
+    matrix: &'a Matrix<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+// derived impls would needlessly require `T: Clone`
+impl<T> Clone for MatrixView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MatrixView<'_, T> {}
+
+/// A mutably borrowed rectangular window into a [`Matrix`], indexed relative to its origin
+pub struct MatrixViewMut<'a, T> {
+    matrix: &'a mut Matrix<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+fn check_bounds<T>(matrix: &Matrix<T>, x: usize, y: usize, width: usize, height: usize) {
+    assert!(
+        x + width <= matrix.width() && y + height <= matrix.height(),
+        "View at ({x}, {y}) of size ({width}, {height}) out of range for Matrix with size ({}, {})",
+        matrix.width(),
+        matrix.height()
+    );
+}
+
+/// Relative to absolute coordinates, if `(x, y)` lies in a `width` by `height` window
+fn absolute(
+    (ox, oy): (usize, usize),
+    (width, height): (usize, usize),
+    x: isize,
+    y: isize,
+) -> Option<(usize, usize)> {
+    let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+        return None;
+    };
+    (x < width && y < height).then_some((ox + x, oy + y))
+}
+
+impl<T> Matrix<T> {
+    /// # Panics
+    /// if `y` is out of range
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.height,
+            "Row {y} out of range for Matrix with height {}",
+            self.height
+        );
+        &self.vec[y * self.width..(y + 1) * self.width]
+    }
+
+    /// # Panics
+    /// if `y` is out of range
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(
+            y < self.height,
+            "Row {y} out of range for Matrix with height {}",
+            self.height
+        );
+        &mut self.vec[y * self.width..(y + 1) * self.width]
+    }
+
+    /// # Panics
+    /// if `x` is out of range
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "Column {x} out of range for Matrix with width {}",
+            self.width
+        );
+        self.vec[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// # Panics
+    /// if the window does not fit inside the matrix
+    #[must_use]
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> MatrixView<'_, T> {
+        check_bounds(self, x, y, width, height);
+        MatrixView {
+            matrix: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// # Panics
+    /// if the window does not fit inside the matrix
+    pub fn view_mut(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> MatrixViewMut<'_, T> {
+        check_bounds(self, x, y, width, height);
+        MatrixViewMut {
+            matrix: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, x: isize, y: isize) -> Option<&'a T> {
+        let (x, y) = absolute((self.x, self.y), (self.width, self.height), x, y)?;
+        Some(&self.matrix[(x, y)])
+    }
+
+    /// # Panics
+    /// if `y` is out of range
+    #[must_use]
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(
+            y < self.height,
+            "Row {y} out of range for view with height {}",
+            self.height
+        );
+        &self.matrix.row(self.y + y)[self.x..self.x + self.width]
+    }
+
+    /// # Panics
+    /// if `x` is out of range
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        assert!(
+            x < self.width,
+            "Column {x} out of range for view with width {}",
+            self.width
+        );
+        let matrix = self.matrix;
+        let x = self.x + x;
+        (self.y..self.y + self.height).map(move |y| &matrix[(x, y)])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let view = *self;
+        (0..self.height).map(move |y| view.row(y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        self.rows().flatten()
+    }
+
+    pub fn iter_pos(&self) -> impl Iterator<Item = ((usize, usize), &'a T)> + use<'a, T> {
+        self.rows()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, t)| ((x, y), t)))
+    }
+
+    /// A window inside this view, relative to its origin
+    ///
+    /// # Panics
+    /// if the window does not fit inside the view
+    #[must_use]
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "View at ({x}, {y}) of size ({width}, {height}) out of range for view with size ({}, {})",
+            self.width,
+            self.height
+        );
+        self.matrix.view(self.x + x, self.y + y, width, height)
+    }
+
+    #[must_use]
+    pub fn to_matrix(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::new(self.iter().cloned(), self.width, self.height)
+    }
+}
+
+impl<T> MatrixViewMut<'_, T> {
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub const fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            matrix: self.matrix,
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        let (x, y) = absolute((self.x, self.y), (self.width, self.height), x, y)?;
+        Some(&self.matrix[(x, y)])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        let (x, y) = absolute((self.x, self.y), (self.width, self.height), x, y)?;
+        Some(&mut self.matrix[(x, y)])
+    }
+
+    /// # Panics
+    /// if `y` is out of range
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(
+            y < self.height,
+            "Row {y} out of range for view with height {}",
+            self.height
+        );
+        let (x, width) = (self.x, self.width);
+        &mut self.matrix.row_mut(self.y + y)[x..x + width]
+    }
+
+    pub fn fill(&mut self, val: T)
+    where
+        T: Clone,
+    {
+        for y in 0..self.height {
+            self.row_mut(y).fill(val.clone());
+        }
+    }
+}
+
+impl<T> ops::Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "Index ({x}, {y}) out of range for view with size ({}, {})",
+            self.width,
+            self.height
+        );
+        &self.matrix[(self.x + x, self.y + y)]
+    }
+}
+
+impl<T> ops::Index<Pos<usize>> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<usize>) -> &Self::Output {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> ops::Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "Index ({x}, {y}) out of range for view with size ({}, {})",
+            self.width,
+            self.height
+        );
+        &self.matrix[(self.x + x, self.y + y)]
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "Index ({x}, {y}) out of range for view with size ({}, {})",
+            self.width,
+            self.height
+        );
+        &mut self.matrix[(self.x + x, self.y + y)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_and_cols() {
+        let matrix = Matrix::new(0..6, 3, 2);
+        let rows: Vec<_> = matrix.rows().collect();
+        assert_eq!(rows, [&[0, 1, 2], &[3, 4, 5]]);
+        let cols: Vec<Vec<_>> = matrix.cols().map(|c| c.copied().collect()).collect();
+        assert_eq!(cols, [[0, 3], [1, 4], [2, 5]]);
+    }
+
+    #[test]
+    fn test_view_is_relative() {
+        let matrix = Matrix::new(0..16, 4, 4);
+        let view = matrix.view(1, 2, 2, 2);
+        assert_eq!(view[(0, 0)], 9);
+        assert_eq!(view.get(1, 1), Some(&14));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.get(-1, 0), None);
+        assert_eq!(view.col(1).copied().collect::<Vec<_>>(), [10, 14]);
+        assert_eq!(view.view(1, 0, 1, 2).to_matrix().vec, [10, 14]);
+    }
+
+    #[test]
+    fn test_view_mut() {
+        let mut matrix = Matrix::new(0..9, 3, 3);
+        let mut view = matrix.view_mut(1, 1, 2, 2);
+        view.fill(0);
+        view[(1, 0)] = 42;
+        assert_eq!(matrix.vec, [0, 1, 2, 3, 0, 42, 6, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_view_out_of_bounds() {
+        let matrix = Matrix::new(0..9, 3, 3);
+        let _ = matrix.view(2, 2, 2, 1);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::BTreeMap, env, error::Error, fmt, str::FromStr};
+
+/// The environment variable holding parameters for the runner, such as `width=11 height=7`
+pub const PARAMS_VAR: &str = "AOC_PARAMS";
+
+/// Puzzle parameters that are not part of the input proper, such as grid sizes that differ
+/// between the examples and the real inputs
+///
+/// They are read from an optional first line of the input such as `@ width=11 height=7`, days
+/// ignoring the keys they do not know.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamsError {
+    /// Not of the form `key=value`
+    Malformed(String),
+    /// A value that cannot be parsed as the parameter's type
+    Invalid { key: String, value: String },
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(param) => write!(f, "parameter {param:?} should be key=value"),
+            Self::Invalid { key, value } => {
+                write!(f, "invalid value {value:?} for parameter {key}")
+            }
+        }
+    }
+}
+
+impl Error for ParamsError {}
+
+impl Params {
+    /// Parses whitespace separated `key=value` pairs
+    ///
+    /// # Errors
+    /// if a pair has no `=` or an empty key
+    pub fn parse(text: &str) -> Result<Self, ParamsError> {
+        text.split_whitespace()
+            .map(|param| {
+                param
+                    .split_once('=')
+                    .filter(|(key, _)| !key.is_empty())
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .ok_or_else(|| ParamsError::Malformed(param.to_string()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Splits the `@` header line, if any, off an input
+    ///
+    /// # Errors
+    /// if the header is malformed
+    pub fn split_header(input: &str) -> Result<(Self, &str), ParamsError> {
+        match input.strip_prefix('@') {
+            Some(rest) => {
+                let (header, body) = rest.split_once('\n').unwrap_or((rest, ""));
+                Ok((Self::parse(header)?, body))
+            }
+            None => Ok((Self::default(), input)),
+        }
+    }
+
+    /// The parameters of [`PARAMS_VAR`], none if it is not set
+    ///
+    /// # Errors
+    /// if the variable is malformed
+    pub fn from_env() -> Result<Self, ParamsError> {
+        env::var(PARAMS_VAR).map_or_else(|_| Ok(Self::default()), |params| Self::parse(&params))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    /// Adds the parameters of `other`, which take precedence
+    pub fn extend(&mut self, other: &Self) {
+        self.0
+            .extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// The value of `key`, or `default` if it is not set
+    ///
+    /// # Errors
+    /// if the value cannot be parsed as a `T`
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, ParamsError> {
+        self.0.get(key).map_or(Ok(default), |value| {
+            value.parse().map_err(|_| ParamsError::Invalid {
+                key: key.to_string(),
+                value: value.clone(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_header() {
+        let (params, body) = Params::split_header("@ width=11 height=7\np=0,4 v=3,-3").unwrap();
+        assert_eq!(body, "p=0,4 v=3,-3");
+        assert_eq!(params.get_or("width", 101), Ok(11));
+        assert_eq!(params.get_or("height", 103), Ok(7));
+        assert_eq!(params.get_or("depth", 5), Ok(5));
+
+        let (params, body) = Params::split_header("1,2\n3,4").unwrap();
+        assert!(params.is_empty());
+        assert_eq!(body, "1,2\n3,4");
+        assert_eq!(
+            Params::split_header("@ width\n"),
+            Err(ParamsError::Malformed("width".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let params = Params::parse("width=abc").unwrap();
+        assert_eq!(
+            params.get_or("width", 101),
+            Err(ParamsError::Invalid {
+                key: "width".to_string(),
+                value: "abc".to_string()
+            })
+        );
+    }
+}
@@ -1,9 +1,15 @@
 use std::{
     fmt::Debug,
-    ops::{Add, Mul, Sub},
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
-use num_traits::Signed;
+use num_traits::{Signed, WrappingAdd};
+
+mod direction;
+mod geometry;
+
+pub use direction::{Compass, Direction, Direction8, ParseDirectionError};
 
 #[derive(Hash, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Pos<T> {
@@ -12,24 +18,82 @@ pub struct Pos<T> {
 }
 
 impl<T> Pos<T> {
+    /// Converts both coordinates, failing if either does not fit in a `U`
+    ///
+    /// # Errors
+    /// the conversion error of the first coordinate that does not fit
+    pub fn try_cast<U>(self) -> Result<Pos<U>, T::Error>
+    where
+        T: TryInto<U>,
+    {
+        Ok(Pos {
+            x: self.x.try_into()?,
+            y: self.y.try_into()?,
+        })
+    }
+
+    /// # Errors
+    /// if a coordinate does not fit in a `usize`
+    pub fn try_to_usize(self) -> Result<Pos<usize>, T::Error>
+    where
+        T: TryInto<usize>,
+    {
+        self.try_cast()
+    }
+
+    /// # Errors
+    /// if a coordinate does not fit in an `isize`
+    pub fn try_to_isize(self) -> Result<Pos<isize>, T::Error>
+    where
+        T: TryInto<isize>,
+    {
+        self.try_cast()
+    }
+
+    /// # Panics
+    /// if a coordinate does not fit in a `usize`
     pub fn to_usize(self) -> Pos<usize>
     where
         T: TryInto<usize>,
         T::Error: Debug,
     {
-        let x = self.x.try_into().unwrap();
-        let y = self.y.try_into().unwrap();
-        Pos { x, y }
+        self.try_to_usize().unwrap()
     }
 
+    /// # Panics
+    /// if a coordinate does not fit in an `isize`
     pub fn to_isize(self) -> Pos<isize>
     where
         T: TryInto<isize>,
         T::Error: Debug,
     {
-        let x = self.x.try_into().unwrap();
-        let y = self.y.try_into().unwrap();
-        Pos { x, y }
+        self.try_to_isize().unwrap()
+    }
+
+    /// Component-wise minimum
+    #[must_use]
+    pub fn min(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        Self::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Component-wise maximum
+    #[must_use]
+    pub fn max(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        Self::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    #[must_use]
+    pub fn wrapping_add(self, other: Self) -> Self
+    where
+        T: WrappingAdd + Copy,
+    {
+        Self::new(self.x.wrapping_add(&other.x), self.y.wrapping_add(&other.y))
     }
 }
 
@@ -37,6 +101,35 @@ impl<T: Add + Signed> Pos<T> {
     pub fn l1_norm(&self) -> T {
         self.x.abs() + self.y.abs()
     }
+
+    /// Component-wise absolute value
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+}
+
+impl Pos<usize> {
+    /// `self + delta`, or `None` if a coordinate would overflow or become negative
+    #[must_use]
+    pub const fn checked_add_signed(self, delta: Pos<isize>) -> Option<Self> {
+        match (
+            self.x.checked_add_signed(delta.x),
+            self.y.checked_add_signed(delta.y),
+        ) {
+            (Some(x), Some(y)) => Some(Self::new(x, y)),
+            _ => None,
+        }
+    }
+
+    /// `self + delta`, each coordinate clamped to `0..=usize::MAX`
+    #[must_use]
+    pub const fn saturating_add_signed(self, delta: Pos<isize>) -> Self {
+        Self::new(
+            self.x.saturating_add_signed(delta.x),
+            self.y.saturating_add_signed(delta.y),
+        )
+    }
 }
 
 impl<T> Pos<T> {
@@ -61,6 +154,29 @@ impl<T> From<Pos<T>> for (T, T) {
     }
 }
 
+macro_rules! impl_try_from_pos {
+    ($($from:ty => $to:ty),* $(,)?) => {
+        $(
+            impl TryFrom<Pos<$from>> for Pos<$to> {
+                type Error = TryFromIntError;
+
+                fn try_from(value: Pos<$from>) -> Result<Self, Self::Error> {
+                    value.try_cast()
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_pos!(
+    isize => usize,
+    usize => isize,
+    i64 => usize,
+    usize => i64,
+    i64 => isize,
+    isize => i64,
+);
+
 impl<T> Add for Pos<T>
 where
     T: Add<Output = T>,
@@ -100,3 +216,80 @@ where
         }
     }
 }
+
+impl<T> Neg for Pos<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> AddAssign for Pos<T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T> SubAssign for Pos<T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_add_signed() {
+        let pos = Pos::new(1_usize, 2);
+        assert_eq!(
+            pos.checked_add_signed(Pos::new(-1, 3)),
+            Some(Pos::new(0, 5))
+        );
+        assert_eq!(pos.checked_add_signed(Pos::new(-2, 0)), None);
+        assert_eq!(pos.saturating_add_signed(Pos::new(-2, -1)), Pos::new(0, 1));
+        assert_eq!(
+            Pos::new(usize::MAX, 0).wrapping_add(Pos::new(2, 1)),
+            Pos::new(1, 1)
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let pos: Result<Pos<usize>, _> = Pos::new(-1_isize, 2).try_into();
+        assert!(pos.is_err());
+        let pos: Pos<usize> = Pos::new(3_i64, 2).try_into().unwrap();
+        assert_eq!(pos, Pos::new(3, 2));
+        assert_eq!(Pos::new(4_usize, 5).try_to_isize(), Ok(Pos::new(4, 5)));
+        assert!(Pos::new(-4_i64, 5).try_to_usize().is_err());
+    }
+
+    #[test]
+    fn test_componentwise() {
+        let (a, b) = (Pos::new(-3, 4), Pos::new(2, -5));
+        assert_eq!(a.min(b), Pos::new(-3, -5));
+        assert_eq!(a.max(b), Pos::new(2, 4));
+        assert_eq!(b.abs(), Pos::new(2, 5));
+        assert_eq!(-a, Pos::new(3, -4));
+        let mut c = a;
+        c += b;
+        assert_eq!(c, Pos::new(-1, -1));
+        c -= b;
+        assert_eq!(c, a);
+    }
+}
@@ -0,0 +1,311 @@
+use std::{error::Error, fmt, ops::Add, str::FromStr};
+
+use super::Pos;
+
+/// A set of directions one can move in on a grid
+pub trait Compass: Copy + Eq + 'static {
+    /// Every direction of the set, in clockwise order starting from north
+    const ALL: &'static [Self];
+
+    fn delta(self) -> Pos<isize>;
+    #[must_use]
+    fn turn_clockwise(self) -> Self;
+    #[must_use]
+    fn turn_counter_clockwise(self) -> Self;
+    #[must_use]
+    fn opposite(self) -> Self;
+}
+
+#[derive(Hash, Ord, PartialOrd, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    const fn unit(self) -> (i8, i8) {
+        match self {
+            Self::N => (0, -1),
+            Self::E => (1, 0),
+            Self::S => (0, 1),
+            Self::W => (-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub const fn delta(self) -> Pos<isize> {
+        let (x, y) = self.unit();
+        Pos::new(x as isize, y as isize)
+    }
+
+    #[must_use]
+    pub const fn turn_clockwise(self) -> Self {
+        match self {
+            Self::N => Self::E,
+            Self::E => Self::S,
+            Self::S => Self::W,
+            Self::W => Self::N,
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_counter_clockwise(self) -> Self {
+        match self {
+            Self::N => Self::W,
+            Self::E => Self::N,
+            Self::S => Self::E,
+            Self::W => Self::S,
+        }
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::N => Self::S,
+            Self::E => Self::W,
+            Self::S => Self::N,
+            Self::W => Self::E,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::N | Self::S)
+    }
+}
+
+impl Compass for Direction {
+    const ALL: &'static [Self] = &Self::ALL;
+
+    fn delta(self) -> Pos<isize> {
+        self.delta()
+    }
+
+    fn turn_clockwise(self) -> Self {
+        self.turn_clockwise()
+    }
+
+    fn turn_counter_clockwise(self) -> Self {
+        self.turn_counter_clockwise()
+    }
+
+    fn opposite(self) -> Self {
+        self.opposite()
+    }
+}
+
+#[derive(Hash, Ord, PartialOrd, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    const fn unit(self) -> (i8, i8) {
+        match self {
+            Self::N => (0, -1),
+            Self::NE => (1, -1),
+            Self::E => (1, 0),
+            Self::SE => (1, 1),
+            Self::S => (0, 1),
+            Self::SW => (-1, 1),
+            Self::W => (-1, 0),
+            Self::NW => (-1, -1),
+        }
+    }
+
+    #[must_use]
+    pub const fn delta(self) -> Pos<isize> {
+        let (x, y) = self.unit();
+        Pos::new(x as isize, y as isize)
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Turns by 45 degrees
+    #[must_use]
+    pub const fn turn_clockwise(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Turns by 45 degrees
+    #[must_use]
+    pub const fn turn_counter_clockwise(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    #[must_use]
+    pub const fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl Compass for Direction8 {
+    const ALL: &'static [Self] = &Self::ALL;
+
+    fn delta(self) -> Pos<isize> {
+        self.delta()
+    }
+
+    fn turn_clockwise(self) -> Self {
+        self.turn_clockwise()
+    }
+
+    fn turn_counter_clockwise(self) -> Self {
+        self.turn_counter_clockwise()
+    }
+
+    fn opposite(self) -> Self {
+        self.opposite()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::N => Self::N,
+            Direction::E => Self::E,
+            Direction::S => Self::S,
+            Direction::W => Self::W,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError(String);
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a direction", self.0)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    /// Accepts both arrows (`^>v<`) and compass points (`NESW`)
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' => Ok(Self::N),
+            '>' | 'E' => Ok(Self::E),
+            'v' | 'S' => Ok(Self::S),
+            '<' | 'W' => Ok(Self::W),
+            _ => Err(ParseDirectionError(value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into().map_err(|_| ParseDirectionError(s.to_string())),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::NE),
+            "SE" => Ok(Self::SE),
+            "SW" => Ok(Self::SW),
+            "NW" => Ok(Self::NW),
+            _ => s.parse::<Direction>().map(Self::from),
+        }
+    }
+}
+
+impl<T> Add<Direction> for Pos<T>
+where
+    T: Add<Output = T> + From<i8>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        let (x, y) = rhs.unit();
+        Self::new(self.x + x.into(), self.y + y.into())
+    }
+}
+
+impl<T> Add<Direction8> for Pos<T>
+where
+    T: Add<Output = T> + From<i8>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        let (x, y) = rhs.unit();
+        Self::new(self.x + x.into(), self.y + y.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::try_from('^'), Ok(Direction::N));
+        assert_eq!("W".parse(), Ok(Direction::W));
+        assert_eq!("<".parse(), Ok(Direction8::W));
+        assert_eq!("SW".parse(), Ok(Direction8::SW));
+        assert!("x".parse::<Direction>().is_err());
+        assert!("NN".parse::<Direction8>().is_err());
+    }
+
+    #[test]
+    fn test_rotations() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_clockwise().turn_counter_clockwise(), d);
+            assert_eq!(d.turn_clockwise().turn_clockwise(), d.opposite());
+            assert_eq!(d.delta() + d.opposite().delta(), Pos::new(0, 0));
+        }
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_clockwise().turn_counter_clockwise(), d);
+            assert_eq!(d.delta() + d.opposite().delta(), Pos::new(0, 0));
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(Pos::new(2, 2) + Direction::N, Pos::new(2, 1));
+        assert_eq!(Pos::new(2, 2) + Direction8::SW, Pos::new(1, 3));
+        assert_eq!(Pos::new(2_i64, 2) + Direction::W, Pos::new(1, 2));
+    }
+}
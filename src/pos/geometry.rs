@@ -0,0 +1,169 @@
+use num_traits::{PrimInt, Signed};
+
+use crate::utils;
+
+use super::Pos;
+
+/// `lo..=hi`, for any integer type
+fn inclusive<T: PrimInt>(lo: T, hi: T) -> impl Iterator<Item = T> {
+    std::iter::successors((lo <= hi).then_some(lo), move |&t| {
+        (t < hi).then(|| t + T::one())
+    })
+}
+
+impl<T: PrimInt + Signed> Pos<T> {
+    /// The largest absolute coordinate
+    pub fn linf_norm(&self) -> T {
+        self.x.abs().max(self.y.abs())
+    }
+
+    pub fn manhattan_to(&self, other: &Self) -> T {
+        (*self - *other).l1_norm()
+    }
+
+    /// The number of king moves between the two positions
+    pub fn chebyshev_to(&self, other: &Self) -> T {
+        (*self - *other).linf_norm()
+    }
+
+    pub fn squared_euclidean_to(&self, other: &Self) -> T {
+        let d = *self - *other;
+        d.dot(&d)
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, positive when `other` is counter-clockwise from
+    /// `self` in a y-up frame
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// The smallest vector with the same direction, with coprime coordinates
+    ///
+    /// The zero vector is its own reduction.
+    #[must_use]
+    pub fn reduced(&self) -> Self {
+        let g = utils::gcd(self.x, self.y);
+        if g.is_zero() {
+            *self
+        } else {
+            Self::new(self.x / g, self.y / g)
+        }
+    }
+
+    /// Whether the three positions lie on a same line
+    pub fn are_collinear(a: &Self, b: &Self, c: &Self) -> bool {
+        (*b - *a).cross(&(*c - *a)).is_zero()
+    }
+
+    /// The cells a rasterized segment from `self` to `end` goes through, both included, as
+    /// given by Bresenham's algorithm
+    pub fn line_to(&self, end: &Self) -> impl Iterator<Item = Self> {
+        let end = *end;
+        let (dx, dy) = ((end.x - self.x).abs(), -(end.y - self.y).abs());
+        let (sx, sy) = ((end.x - self.x).signum(), (end.y - self.y).signum());
+        let two = T::one() + T::one();
+        let mut err = dx + dy;
+        let mut next = Some(*self);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = (current != end).then(|| {
+                let e2 = two * err;
+                let mut p = current;
+                if e2 >= dy {
+                    err = err + dy;
+                    p.x = p.x + sx;
+                }
+                if e2 <= dx {
+                    err = err + dx;
+                    p.y = p.y + sy;
+                }
+                p
+            });
+            Some(current)
+        })
+    }
+
+    /// The lattice points lying exactly on the segment from `self` to `end`, both included
+    pub fn lattice_points_to(&self, end: &Self) -> impl Iterator<Item = Self> {
+        let end = *end;
+        let step = (end - *self).reduced();
+        let mut next = Some(*self);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = (current != end).then(|| current + step);
+            Some(current)
+        })
+    }
+
+    /// Every position at a manhattan distance of at most `radius`, including `self`
+    pub fn l1_ball(&self, radius: T) -> impl Iterator<Item = Self> {
+        let center = *self;
+        inclusive(-radius, radius).flat_map(move |dy| {
+            let rem = radius - dy.abs();
+            inclusive(-rem, rem).map(move |dx| Self::new(center.x + dx, center.y + dy))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        let (a, b) = (Pos::new(1, -2), Pos::new(-3, 1));
+        assert_eq!(a.manhattan_to(&b), 7);
+        assert_eq!(a.chebyshev_to(&b), 4);
+        assert_eq!(a.squared_euclidean_to(&b), 25);
+        assert_eq!(a.dot(&b), -5);
+        assert_eq!(a.cross(&b), -5);
+    }
+
+    #[test]
+    fn test_reduced_and_collinear() {
+        assert_eq!(Pos::new(6, -4).reduced(), Pos::new(3, -2));
+        assert_eq!(Pos::new(0, -5).reduced(), Pos::new(0, -1));
+        assert_eq!(Pos::new(0, 0).reduced(), Pos::new(0, 0));
+        assert!(Pos::are_collinear(
+            &Pos::new(0, 0),
+            &Pos::new(2, 1),
+            &Pos::new(-4, -2)
+        ));
+        assert!(!Pos::are_collinear(
+            &Pos::new(0, 0),
+            &Pos::new(2, 1),
+            &Pos::new(4, 3)
+        ));
+    }
+
+    #[test]
+    fn test_lines() {
+        let line: Vec<_> = Pos::new(0, 0).line_to(&Pos::new(4, 2)).collect();
+        assert_eq!(
+            line,
+            [
+                Pos::new(0, 0),
+                Pos::new(1, 1),
+                Pos::new(2, 1),
+                Pos::new(3, 2),
+                Pos::new(4, 2)
+            ]
+        );
+        let points: Vec<_> = Pos::new(0, 0).lattice_points_to(&Pos::new(4, 2)).collect();
+        assert_eq!(points, [Pos::new(0, 0), Pos::new(2, 1), Pos::new(4, 2)]);
+        assert_eq!(Pos::new(3, 3).line_to(&Pos::new(3, 3)).count(), 1);
+    }
+
+    #[test]
+    fn test_l1_ball() {
+        let center = Pos::new(5_i64, -2);
+        let ball: Vec<_> = center.l1_ball(2).collect();
+        assert_eq!(ball.len(), 13);
+        assert!(ball.iter().all(|p| p.manhattan_to(&center) <= 2));
+        assert_eq!(center.l1_ball(0).collect::<Vec<_>>(), [center]);
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num_traits::Zero;
+
+/// Outcome of a graph search: the distance to every settled state, the predecessor DAG of all
+/// shortest paths, and the goal states reached at minimal cost.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    dist: HashMap<S, C>,
+    prev: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S, C> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    #[must_use]
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    #[must_use]
+    pub const fn distances(&self) -> &HashMap<S, C> {
+        &self.dist
+    }
+
+    /// Goal states reached at the minimal cost, in the order they were settled
+    #[must_use]
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    #[must_use]
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    #[must_use]
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal().and_then(|g| self.distance(g))
+    }
+
+    /// Every predecessor of `state` that lies on a shortest path to it
+    #[must_use]
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.prev.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from a start state to `to`, both ends included
+    #[must_use]
+    pub fn path_to(&self, to: &S) -> Option<Vec<S>> {
+        self.dist.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(p) = self.predecessors(path.last()?).first() {
+            path.push(p.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One shortest path from a start state to the first goal
+    #[must_use]
+    pub fn shortest_path(&self) -> Option<Vec<S>> {
+        self.goal().and_then(|g| self.path_to(g))
+    }
+
+    /// Every state lying on at least one shortest path to any of the `targets`
+    pub fn on_shortest_paths<'a, I>(&self, targets: I) -> HashSet<S>
+    where
+        I: IntoIterator<Item = &'a S>,
+        S: 'a,
+    {
+        let mut visited = HashSet::new();
+        let mut to_visit: Vec<_> = targets.into_iter().cloned().collect();
+        while let Some(state) = to_visit.pop() {
+            if visited.insert(state.clone()) {
+                to_visit.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        visited
+    }
+
+    /// Every state lying on at least one shortest path to a goal
+    #[must_use]
+    pub fn on_any_shortest_path(&self) -> HashSet<S> {
+        self.on_shortest_paths(&self.goals)
+    }
+
+    /// Number of distinct shortest paths from the start states to `to`
+    #[must_use]
+    pub fn count_paths_to(&self, to: &S) -> usize {
+        fn count<S: Clone + Eq + Hash>(
+            prev: &HashMap<S, Vec<S>>,
+            state: &S,
+            memo: &mut HashMap<S, usize>,
+        ) -> usize {
+            if let Some(&n) = memo.get(state) {
+                return n;
+            }
+            let n = prev
+                .get(state)
+                .map_or(1, |preds| preds.iter().map(|p| count(prev, p, memo)).sum());
+            memo.insert(state.clone(), n);
+            n
+        }
+        if !self.dist.contains_key(to) {
+            return 0;
+        }
+        count(&self.prev, to, &mut HashMap::new())
+    }
+}
+
+/// Breadth-first search with unit edge costs.
+///
+/// The search stops once every state at the distance of the closest goal has been settled, so
+/// that all shortest paths to the goals are recorded. With a goal predicate that never holds,
+/// the whole reachable graph is explored.
+pub fn bfs<S, I, FN, FG>(start: S, mut neighbors: FN, mut is_goal: FG) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut prev: HashMap<S, Vec<S>> = HashMap::new();
+    let mut goals = Vec::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let d = dist[&state];
+        if let Some(g) = goals.first() {
+            if d > dist[g] {
+                break;
+            }
+        }
+        if is_goal(&state) {
+            goals.push(state.clone());
+            continue;
+        }
+        for next in neighbors(&state) {
+            match dist.get(&next) {
+                None => {
+                    dist.insert(next.clone(), d + 1);
+                    prev.insert(next.clone(), vec![state.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&dn) if dn == d + 1 => {
+                    prev.entry(next).or_default().push(state.clone());
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    SearchResult { dist, prev, goals }
+}
+
+struct HeapEntry<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for HeapEntry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            // on a tie the cheaper state may be a predecessor of the other, so it goes first
+            .then_with(|| other.cost.cmp(&self.cost))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for HeapEntry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for HeapEntry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for HeapEntry<S, C> {}
+
+/// Dijkstra's algorithm from several start states at once, see [`astar`]
+pub fn dijkstra<S, C, I, FN, FG>(
+    starts: impl IntoIterator<Item = S>,
+    successors: FN,
+    is_goal: FG,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+    FN: FnMut(&S) -> I,
+    FG: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search from several start states at once.
+///
+/// `successors` yields each neighbour of a state along with the cost of the edge leading to it,
+/// which must not be negative. Through zero-cost edges, only the predecessors settled first are
+/// recorded.
+/// The `heuristic` must be consistent (never overestimate the cost of an edge plus the remaining
+/// estimate) for the predecessor DAG to hold all shortest paths. The search stops once the
+/// estimates exceed the cost of the best goal found, see [`bfs`].
+pub fn astar<S, C, I, FN, FH, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+    FN: FnMut(&S) -> I,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut dist = HashMap::new();
+    let mut prev: HashMap<S, Vec<S>> = HashMap::new();
+    let mut goals = Vec::new();
+    let mut best = None;
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        dist.insert(start.clone(), C::zero());
+        heap.push(HeapEntry {
+            estimate: heuristic(&start),
+            cost: C::zero(),
+            state: start,
+        });
+    }
+
+    while let Some(HeapEntry {
+        estimate,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        if dist.get(&state).is_some_and(|&d| cost > d) || !settled.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            best = Some(cost);
+            goals.push(state);
+            continue;
+        }
+        for (next, edge) in successors(&state) {
+            let next_cost = cost + edge;
+            match dist.get(&next).map(|d| next_cost.cmp(d)) {
+                None | Some(Ordering::Less) => {
+                    dist.insert(next.clone(), next_cost);
+                    prev.insert(next.clone(), vec![state.clone()]);
+                    heap.push(HeapEntry {
+                        estimate: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        state: next,
+                    });
+                }
+                // a settled state already has all its predecessors, and adding more through
+                // zero-cost edges could close a cycle
+                Some(Ordering::Equal) if !settled.contains(&next) => {
+                    prev.entry(next).or_default().push(state.clone());
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    SearchResult { dist, prev, goals }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_neighbors((x, y): (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|(x, y)| (0..3).contains(x) && (0..3).contains(y))
+    }
+
+    #[test]
+    fn test_bfs_counts_all_shortest_paths() {
+        let result = bfs((0, 0), |&p| grid_neighbors(p), |&p| p == (2, 2));
+        assert_eq!(result.goal_cost(), Some(4));
+        assert_eq!(result.count_paths_to(&(2, 2)), 6);
+        assert_eq!(result.on_any_shortest_path().len(), 9);
+        assert_eq!(result.shortest_path().map(|p| p.len()), Some(5));
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheaper_edges() {
+        // moving right is cheap, moving down is expensive
+        let result = dijkstra(
+            [(0, 0)],
+            |&p: &(i32, i32)| grid_neighbors(p).map(move |n| (n, if n.1 == p.1 { 1 } else { 10 })),
+            |&p| p == (2, 2),
+        );
+        assert_eq!(result.goal_cost(), Some(22));
+        assert_eq!(result.count_paths_to(&(2, 2)), 6);
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let result = astar(
+            [(0, 0)],
+            |&p: &(i32, i32)| grid_neighbors(p).map(|n| (n, 1)),
+            |&(x, y)| (2 - x) + (2 - y),
+            |&p| p == (2, 2),
+        );
+        assert_eq!(result.goal_cost(), Some(4));
+        assert_eq!(result.count_paths_to(&(2, 2)), 6);
+    }
+
+    #[test]
+    fn test_dijkstra_zero_cost_edges() {
+        // 0 and 1 are linked both ways for free, and both lead to 2
+        let result = dijkstra(
+            [0],
+            |&s: &u8| match s {
+                0 => vec![(1, 0), (2, 1)],
+                1 => vec![(0, 0), (2, 1)],
+                _ => vec![],
+            },
+            |&s| s == 2,
+        );
+        assert_eq!(result.goal_cost(), Some(1));
+        assert_eq!(result.predecessors(&0), []);
+        assert_eq!(result.count_paths_to(&2), 2);
+        assert_eq!(result.shortest_path().map(|p| p.len()), Some(2));
+    }
+}
@@ -0,0 +1,218 @@
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day23,
+    day3, day4, day5, day6, day7, day8, day9,
+    params::{Params, ParamsError},
+};
+
+/// The year the solutions of this crate are for
+pub const YEAR: u32 = 2024;
+
+/// The solution to both parts of a day's puzzle
+///
+/// Each day's solution is exported from the crate root, as `DayN`:
+/// ```
+/// use advent_2024::{solution::Solution, Day1};
+///
+/// let input = Day1::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3");
+/// assert_eq!(Day1::part1(&input), 11);
+/// assert_eq!(Day1::part2(&input), Some(31));
+/// ```
+pub trait Solution {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Parses the input once its `@` header is split off, given the parameters it sets, see
+    /// [`Params`]
+    ///
+    /// # Errors
+    /// if a parameter has an invalid value
+    fn parse_body(input: &str, params: &Params) -> Result<Self::Input, ParamsError>;
+
+    /// Parses the input along with its `@` header, if any
+    ///
+    /// # Panics
+    /// if the header is malformed or has an invalid value
+    #[must_use]
+    fn parse(input: &str) -> Self::Input {
+        Self::parse_with(input, &Params::default()).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parses the input, with the parameters of its `@` header overridden by `overrides`
+    ///
+    /// # Errors
+    /// if the header is malformed, or a parameter has an invalid value
+    fn parse_with(input: &str, overrides: &Params) -> Result<Self::Input, ParamsError> {
+        let (mut params, input) = Params::split_header(input)?;
+        params.extend(overrides);
+        Self::parse_body(input, &params)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    /// The answer to part 2, `None` while it is not solved, for which `Part2` can be
+    /// [`Infallible`](std::convert::Infallible)
+    fn part2(_input: &Self::Input) -> Option<Self::Part2> {
+        None
+    }
+}
+
+/// The parsed input of a [`Solver`], only usable with the solver that produced it
+pub struct Parsed(Box<dyn Any>);
+
+/// A type-erased [`Solution`], as stored in the [`REGISTRY`]
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    parse: fn(&str, &Params) -> Result<Parsed, ParamsError>,
+    part1: fn(&Parsed) -> String,
+    part2: fn(&Parsed) -> Option<String>,
+}
+
+fn parse_erased<S: Solution>(input: &str, overrides: &Params) -> Result<Parsed, ParamsError>
+where
+    S::Input: 'static,
+{
+    S::parse_with(input, overrides).map(|input| Parsed(Box::new(input)))
+}
+
+fn input_of<S: Solution>(parsed: &Parsed) -> &S::Input
+where
+    S::Input: 'static,
+{
+    parsed
+        .0
+        .downcast_ref()
+        .expect("input should have been parsed by the same solver")
+}
+
+fn part1_erased<S: Solution>(parsed: &Parsed) -> String
+where
+    S::Input: 'static,
+{
+    S::part1(input_of::<S>(parsed)).to_string()
+}
+
+fn part2_erased<S: Solution>(parsed: &Parsed) -> Option<String>
+where
+    S::Input: 'static,
+{
+    S::part2(input_of::<S>(parsed)).map(|answer| answer.to_string())
+}
+
+impl Solver {
+    #[must_use]
+    pub const fn new<S: Solution>(year: u32, day: u32) -> Self
+    where
+        S::Input: 'static,
+    {
+        Self {
+            year,
+            day,
+            parse: parse_erased::<S>,
+            part1: part1_erased::<S>,
+            part2: part2_erased::<S>,
+        }
+    }
+
+    /// Parses the puzzle input with the parameters of its `@` header, trailing newlines being
+    /// ignored
+    ///
+    /// # Panics
+    /// if the header of the input is malformed or has an invalid value
+    #[must_use]
+    pub fn parse(&self, input: &str) -> Parsed {
+        self.parse_with(input, &Params::default())
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Parses the puzzle input, with the parameters of its `@` header overridden by `overrides`
+    ///
+    /// # Errors
+    /// if the header of the input is malformed, or a parameter has an invalid value
+    pub fn parse_with(&self, input: &str, overrides: &Params) -> Result<Parsed, ParamsError> {
+        (self.parse)(input.trim_end_matches(['\n', '\r']), overrides)
+    }
+
+    /// # Panics
+    /// if `input` was not parsed by this solver
+    #[must_use]
+    pub fn part1(&self, input: &Parsed) -> String {
+        (self.part1)(input)
+    }
+
+    /// The answer to part 2, `None` while it is not solved
+    ///
+    /// # Panics
+    /// if `input` was not parsed by this solver
+    #[must_use]
+    pub fn part2(&self, input: &Parsed) -> Option<String> {
+        (self.part2)(input)
+    }
+
+    /// Solves `part`, or returns `None` if there is no such part or it is not solved
+    ///
+    /// # Panics
+    /// if `input` was not parsed by this solver
+    #[must_use]
+    pub fn solve(&self, part: u32, input: &Parsed) -> Option<String> {
+        match part {
+            1 => Some(self.part1(input)),
+            2 => self.part2(input),
+            _ => None,
+        }
+    }
+}
+
+/// Every solution of the crate, ordered by year then day
+pub static REGISTRY: &[Solver] = &[
+    Solver::new::<day1::Day1>(YEAR, 1),
+    Solver::new::<day2::Day2>(YEAR, 2),
+    Solver::new::<day3::Day3>(YEAR, 3),
+    Solver::new::<day4::Day4>(YEAR, 4),
+    Solver::new::<day5::Day5>(YEAR, 5),
+    Solver::new::<day6::Day6>(YEAR, 6),
+    Solver::new::<day7::Day7>(YEAR, 7),
+    Solver::new::<day8::Day8>(YEAR, 8),
+    Solver::new::<day9::Day9>(YEAR, 9),
+    Solver::new::<day10::Day10>(YEAR, 10),
+    Solver::new::<day11::Day11>(YEAR, 11),
+    Solver::new::<day12::Day12>(YEAR, 12),
+    Solver::new::<day13::Day13>(YEAR, 13),
+    Solver::new::<day14::Day14>(YEAR, 14),
+    Solver::new::<day15::Day15>(YEAR, 15),
+    Solver::new::<day16::Day16>(YEAR, 16),
+    Solver::new::<day17::Day17>(YEAR, 17),
+    Solver::new::<day18::Day18>(YEAR, 18),
+    Solver::new::<day19::Day19>(YEAR, 19),
+    Solver::new::<day20::Day20>(YEAR, 20),
+    Solver::new::<day23::Day23>(YEAR, 23),
+];
+
+#[must_use]
+pub fn find(year: u32, day: u32) -> Option<&'static Solver> {
+    REGISTRY.iter().find(|s| s.year == year && s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted_and_unique() {
+        assert!(REGISTRY
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    }
+
+    #[test]
+    fn test_solve_through_registry() {
+        let solver = find(YEAR, 1).unwrap();
+        let input = solver.parse(day1::EXAMPLE);
+        assert_eq!(solver.solve(1, &input).as_deref(), Some("11"));
+        assert_eq!(solver.solve(2, &input).as_deref(), Some("31"));
+        assert_eq!(solver.solve(3, &input), None);
+    }
+}
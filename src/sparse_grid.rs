@@ -0,0 +1,241 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt, ops,
+};
+
+use crate::{matrix::Matrix, pos::Pos};
+
+/// An unbounded grid storing only the cells that were set, and keeping track of their bounding box
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos<i64>, T>,
+    bounds: Option<BoundingBox>,
+}
+
+type BoundingBox = (Pos<i64>, Pos<i64>);
+
+fn expand(bounds: Option<BoundingBox>, pos: Pos<i64>) -> BoundingBox {
+    bounds.map_or((pos, pos), |(min, max)| {
+        (
+            Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+            Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+        )
+    })
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps the cells of `matrix` for which `keep` holds, the cell at `(0, 0)` going to `offset`
+    ///
+    /// # Panics
+    /// if the matrix is too large for its positions to fit in an `i64`
+    pub fn from_matrix<F>(matrix: Matrix<T>, offset: Pos<i64>, mut keep: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let width = matrix.width();
+        matrix
+            .vec
+            .into_iter()
+            .enumerate()
+            .filter(|(_, t)| keep(t))
+            .map(|(i, t)| {
+                let pos: Pos<i64> = Pos::new(i % width, i / width).try_into().unwrap();
+                (pos + offset, t)
+            })
+            .collect()
+    }
+
+    /// The dense matrix spanning the bounding box, with `fill` in the unset cells, along with
+    /// the position its `(0, 0)` cell corresponds to
+    ///
+    /// Returns `None` if the grid is empty.
+    ///
+    /// # Panics
+    /// if the bounding box is too large to fit in memory
+    #[must_use]
+    pub fn to_matrix(&self, fill: T) -> Option<(Matrix<T>, Pos<i64>)>
+    where
+        T: Clone,
+    {
+        let (min, max) = self.bounds?;
+        let Pos {
+            x: width,
+            y: height,
+        } = (max - min + Pos::new(1, 1)).to_usize();
+        let mut matrix = Matrix::new(std::iter::repeat_n(fill, width * height), width, height);
+        for (&pos, t) in &self.cells {
+            matrix[(pos - min).to_usize()] = t.clone();
+        }
+        Some((matrix, min))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest and largest corners of the bounding box, both included
+    #[must_use]
+    pub const fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    #[must_use]
+    pub fn in_bounds(&self, pos: Pos<i64>) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y)
+        })
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos<i64>) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Pos<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    #[must_use]
+    pub fn contains(&self, pos: Pos<i64>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Pos<i64>, val: T) -> Option<T> {
+        self.bounds = Some(expand(self.bounds, pos));
+        self.cells.insert(pos, val)
+    }
+
+    /// Removes a cell, shrinking the bounding box if the cell was on its edge
+    pub fn remove(&mut self, pos: Pos<i64>) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds {
+            if pos.x == min.x || pos.y == min.y || pos.x == max.x || pos.y == max.y {
+                self.bounds = self.cells.keys().fold(None, |b, &p| Some(expand(b, p)));
+            }
+        }
+        Some(removed)
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> hash_map::Iter<'_, Pos<i64>, T> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos<i64>> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+impl<T> FromIterator<(Pos<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos<i64>, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Pos<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Pos<i64>, T)>>(&mut self, iter: I) {
+        for (pos, t) in iter {
+            self.insert(pos, t);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Pos<i64>, &'a T);
+    type IntoIter = hash_map::Iter<'a, Pos<i64>, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> ops::Index<Pos<i64>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos<i64>) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("No cell at {pos:?} in SparseGrid"))
+    }
+}
+
+/// Renders the bounding box, unset cells being drawn as `.`
+impl<T> fmt::Display for SparseGrid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                match self.get(Pos::new(x, y)) {
+                    Some(t) => write!(f, "{t}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_follow_inserts_and_removals() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Pos::new(-2, 3), 'a');
+        grid.insert(Pos::new(1, -1), 'b');
+        assert_eq!(grid.bounds(), Some((Pos::new(-2, -1), Pos::new(1, 3))));
+        assert!(grid.in_bounds(Pos::new(0, 0)));
+        grid.remove(Pos::new(-2, 3));
+        assert_eq!(grid.bounds(), Some((Pos::new(1, -1), Pos::new(1, -1))));
+        grid.remove(Pos::new(1, -1));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let grid: SparseGrid<_> = [(Pos::new(-1, -1), '#'), (Pos::new(1, 0), '#')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.to_string(), "#..\n..#\n");
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix = Matrix::new("#..#.#".chars(), 3, 2);
+        let grid = SparseGrid::from_matrix(matrix, Pos::new(-5, 10), |c| *c == '#');
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(Pos::new(-5, 10)));
+        let (dense, offset) = grid.to_matrix('.').unwrap();
+        assert_eq!(offset, Pos::new(-5, 10));
+        assert_eq!(dense.vec, "#..#.#".chars().collect::<Vec<_>>());
+    }
+}
@@ -1,33 +1,228 @@
+use std::{error::Error, fmt};
+
+use num_traits::PrimInt;
+
 use crate::{matrix::Matrix, pos::Pos};
 
-fn width_height(input: &str) -> (usize, usize) {
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
-    (width, height)
+pub mod linalg;
+
+/// An iterator over `items`, running on the rayon thread pool with the `parallel` feature
+///
+/// Only the adapters shared by [`Iterator`] and rayon's `ParallelIterator` can follow, such as
+/// `filter`, `map`, `count` or `sum`, so that both give the same result whatever the order the
+/// items are processed in.
+macro_rules! par_iter {
+    ($items:expr) => {{
+        #[cfg(feature = "parallel")]
+        let items = rayon::iter::IntoParallelIterator::into_par_iter($items);
+        #[cfg(not(feature = "parallel"))]
+        let items = IntoIterator::into_iter($items);
+        items
+    }};
+}
+
+pub(crate) use par_iter;
+
+/// rayon's `map_init` for the sequential [`par_iter!`], where a single state is enough
+pub trait MapInit: Iterator + Sized {
+    fn map_init<T, R>(
+        self,
+        init: impl FnOnce() -> T,
+        mut map_op: impl FnMut(&mut T, Self::Item) -> R,
+    ) -> impl Iterator<Item = R> {
+        let mut state = init();
+        self.map(move |item| map_op(&mut state, item))
+    }
+}
+
+impl<I: Iterator> MapInit for I {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    /// The input did not contain a single non-empty line
+    Empty,
+    /// A line does not have the same width as the first one
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The mapping closure rejected a character
+    InvalidChar {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid input is empty"),
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ragged rows: line {line} has width {found}, expected {expected}"
+            ),
+            Self::InvalidChar {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "unexpected character {found:?} at line {line}, column {column}"
+            ),
+        }
+    }
+}
+
+impl Error for GridParseError {}
+
+/// Parses a rectangular grid, calling `map_each_char` with the position of every character.
+///
+/// Line endings (`\n` or `\r\n`), trailing whitespace on each line and trailing blank lines are
+/// ignored. Lines and columns in the returned error are 1-based.
+///
+/// # Errors
+/// if the input is empty, if a line's width differs from the first line's, or if
+/// `map_each_char` returns `None` for some character
+pub fn try_parse_grid_using_pos<F, T>(
+    input: &str,
+    mut map_each_char: F,
+) -> Result<Matrix<T>, GridParseError>
+where
+    F: FnMut(Pos<usize>, char) -> Option<T>,
+{
+    let lines: Vec<_> = input.lines().map(str::trim_end).collect();
+    let height = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(0, |i| i + 1);
+    let lines = &lines[..height];
+    let width = lines
+        .first()
+        .map(|l| l.chars().count())
+        .ok_or(GridParseError::Empty)?;
+
+    let mut items = Vec::with_capacity(width * height);
+    for (y, line) in lines.iter().enumerate() {
+        let found = line.chars().count();
+        if found != width {
+            return Err(GridParseError::Ragged {
+                line: y + 1,
+                expected: width,
+                found,
+            });
+        }
+        for (x, c) in line.chars().enumerate() {
+            let item = map_each_char(Pos::new(x, y), c).ok_or(GridParseError::InvalidChar {
+                line: y + 1,
+                column: x + 1,
+                found: c,
+            })?;
+            items.push(item);
+        }
+    }
+    Ok(Matrix::new(items, width, height))
+}
+
+/// Same as [`try_parse_grid_using_pos`], without the position of each character
+///
+/// # Errors
+/// see [`try_parse_grid_using_pos`]
+pub fn try_parse_grid<F, T>(input: &str, mut map_each_char: F) -> Result<Matrix<T>, GridParseError>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    try_parse_grid_using_pos(input, |_, c| map_each_char(c))
 }
 
+/// # Panics
+/// if the input is not a well-formed grid, see [`try_parse_grid`]
 pub fn parse_grid<F, T>(input: &str, map_each_char: F) -> Matrix<T>
 where
     F: Fn(char) -> T,
 {
-    let (width, height) = width_height(input);
-    let items = input
-        .chars()
-        .filter(char::is_ascii_graphic)
-        .map(map_each_char);
-    Matrix::new(items, width, height)
+    try_parse_grid(input, |c| Some(map_each_char(c))).unwrap_or_else(|err| panic!("{err}"))
 }
 
+/// # Panics
+/// if the input is not a well-formed grid, see [`try_parse_grid_using_pos`]
 pub fn parse_grid_using_pos<F, T>(input: &str, mut map_each_char: F) -> Matrix<T>
 where
     F: FnMut(Pos<usize>, char) -> T,
 {
-    let (width, height) = width_height(input);
-    let items = input
-        .chars()
-        .filter(char::is_ascii_graphic)
-        .enumerate()
-        .map(|(i, c)| (Pos::new(i % width, i / width), c))
-        .map(|(pos, c)| map_each_char(pos, c));
-    Matrix::new(items, width, height)
+    try_parse_grid_using_pos(input, |pos, c| Some(map_each_char(pos, c)))
+        .unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Greatest common divisor, always non-negative; `gcd(0, 0)` is `0`
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::zero() {
+        (a, b) = (b, a % b);
+    }
+    if a < T::zero() {
+        T::zero() - a
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crlf_and_trailing_whitespace() {
+        let grid = try_parse_grid("ab  \r\ncd\r\n\r\n", Some).unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.vec, vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn test_ragged() {
+        assert_eq!(
+            try_parse_grid("abc\nab\nabc", Some),
+            Err(GridParseError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_char() {
+        let result = try_parse_grid("#.\n.x", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+        assert_eq!(
+            result,
+            Err(GridParseError::InvalidChar {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(try_parse_grid("\n\n", Some), Err(GridParseError::Empty));
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-4_i64, 6), 2);
+        assert_eq!(gcd(0_usize, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+    }
 }
@@ -0,0 +1,410 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_traits::{PrimInt, Signed};
+
+use crate::matrix::Matrix;
+
+use super::gcd;
+
+/// An exact fraction, always kept reduced with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: PrimInt + Signed> Ratio<T> {
+    /// # Panics
+    /// if `denom` is zero
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "Ratio with a zero denominator");
+        let g = gcd(numer, denom);
+        let sign = denom.signum();
+        Self {
+            numer: numer / g * sign,
+            denom: denom / g * sign,
+        }
+    }
+
+    pub fn from_integer(n: T) -> Self {
+        Self {
+            numer: n,
+            denom: T::one(),
+        }
+    }
+
+    #[must_use]
+    pub fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    #[must_use]
+    pub fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+
+    pub const fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub const fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    /// The value if it is a whole number
+    pub fn to_integer(&self) -> Option<T> {
+        self.is_integer().then_some(self.numer)
+    }
+}
+
+impl<T: PrimInt + Signed> Add for Ratio<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.numer * rhs.denom + rhs.numer * self.denom,
+            self.denom * rhs.denom,
+        )
+    }
+}
+
+impl<T: PrimInt + Signed> Sub for Ratio<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: PrimInt + Signed> Mul for Ratio<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.numer * rhs.numer, self.denom * rhs.denom)
+    }
+}
+
+impl<T: PrimInt + Signed> Div for Ratio<T> {
+    type Output = Self;
+
+    /// # Panics
+    /// if `rhs` is zero
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(self.numer * rhs.denom, self.denom * rhs.numer)
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Ratio<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PrimInt + Signed> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so cross-multiplying keeps the order
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl<T: PrimInt + Signed> From<T> for Ratio<T> {
+    fn from(n: T) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+impl<T: fmt::Display + PrimInt> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// The solutions of a linear system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SystemSolution {
+    Unique(Vec<Ratio<i128>>),
+    None,
+    /// Every `particular + t_1 * directions[0] + ... + t_k * directions[k - 1]` is a solution,
+    /// whatever the rational values of the `t_i`
+    Infinite {
+        particular: Vec<Ratio<i128>>,
+        directions: Vec<Vec<Ratio<i128>>>,
+    },
+}
+
+/// Solves `coefficients * x = rhs` exactly, by Gaussian elimination over the rationals
+///
+/// # Panics
+/// if `rhs` does not have one value per row of `coefficients`
+#[must_use]
+pub fn solve(coefficients: &Matrix<i128>, rhs: &[i128]) -> SystemSolution {
+    let (cols, rows) = (coefficients.width(), coefficients.height());
+    assert_eq!(rhs.len(), rows, "one right-hand side value per equation");
+    // augmented rows, brought to reduced row echelon form
+    let mut system: Vec<Vec<Ratio<i128>>> = coefficients
+        .rows()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().chain([&b]).map(|&n| n.into()).collect())
+        .collect();
+    let mut pivots = Vec::new();
+    for col in 0..cols {
+        let r = pivots.len();
+        let Some(pivot_row) = (r..rows).find(|&i| !system[i][col].is_zero()) else {
+            continue;
+        };
+        system.swap(r, pivot_row);
+        let pivot = system[r][col];
+        for value in &mut system[r] {
+            *value = *value / pivot;
+        }
+        let pivot_row = system[r].clone();
+        for (i, row) in system.iter_mut().enumerate() {
+            let factor = row[col];
+            if i == r || factor.is_zero() {
+                continue;
+            }
+            for (value, &p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = *value - factor * p;
+            }
+        }
+        pivots.push(col);
+    }
+    // rows past the pivots only have zero coefficients left
+    if system[pivots.len()..]
+        .iter()
+        .any(|row| !row[cols].is_zero())
+    {
+        return SystemSolution::None;
+    }
+
+    let mut particular = vec![Ratio::zero(); cols];
+    for (r, &col) in pivots.iter().enumerate() {
+        particular[col] = system[r][cols];
+    }
+    if pivots.len() == cols {
+        return SystemSolution::Unique(particular);
+    }
+    let directions = (0..cols)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut direction = vec![Ratio::zero(); cols];
+            direction[free] = Ratio::one();
+            for (r, &col) in pivots.iter().enumerate() {
+                direction[col] = -system[r][free];
+            }
+            direction
+        })
+        .collect();
+    SystemSolution::Infinite {
+        particular,
+        directions,
+    }
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, `g` being the non-negative gcd of `a` and `b`
+#[must_use]
+pub const fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut prev_rem, mut rem) = (a, b);
+    let (mut prev_x, mut next_x) = (1, 0);
+    let (mut prev_y, mut next_y) = (0, 1);
+    while rem != 0 {
+        let quotient = prev_rem / rem;
+        (prev_rem, rem) = (rem, prev_rem - quotient * rem);
+        (prev_x, next_x) = (next_x, prev_x - quotient * next_x);
+        (prev_y, next_y) = (next_y, prev_y - quotient * next_y);
+    }
+    if prev_rem < 0 {
+        (-prev_rem, -prev_x, -prev_y)
+    } else {
+        (prev_rem, prev_x, prev_y)
+    }
+}
+
+/// The solutions of `x = r1 (mod m1)` and `x = r2 (mod m2)`, as the smallest non-negative one and
+/// the lcm of the moduli they repeat with, `None` if the congruences are incompatible
+///
+/// # Panics
+/// if a modulus is not positive
+#[must_use]
+pub fn crt(r1: i128, m1: i128, r2: i128, m2: i128) -> Option<(i128, i128)> {
+    assert!(m1 > 0 && m2 > 0, "moduli should be positive");
+    let (divisor, inverse, _) = ext_gcd(m1, m2);
+    if (r2 - r1) % divisor != 0 {
+        return None;
+    }
+    let lcm = m1 / divisor * m2;
+    let steps = ((r2 - r1) / divisor * inverse).rem_euclid(m2 / divisor);
+    Some(((r1 + m1 * steps).rem_euclid(lcm), lcm))
+}
+
+/// The non-negative integer solutions `(x, y)` of `a * x + b * y = c`, by increasing `x`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonNegativeSolutions {
+    next: (i128, i128),
+    step: (i128, i128),
+    remaining: i128,
+}
+
+impl Iterator for NonNegativeSolutions {
+    type Item = (i128, i128);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining <= 0 {
+            return None;
+        }
+        let current = self.next;
+        self.next = (current.0 + self.step.0, current.1 - self.step.1);
+        self.remaining -= 1;
+        Some(current)
+    }
+}
+
+impl DoubleEndedIterator for NonNegativeSolutions {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining <= 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some((
+            self.next.0 + self.remaining * self.step.0,
+            self.next.1 - self.remaining * self.step.1,
+        ))
+    }
+}
+
+/// The non-negative integer solutions of `a * x + b * y = c`, which are finitely many
+///
+/// # Panics
+/// if `a` or `b` is not positive
+#[must_use]
+pub fn non_negative_solutions(a: i128, b: i128, c: i128) -> NonNegativeSolutions {
+    assert!(a > 0 && b > 0, "coefficients should be positive");
+    let (divisor, bezout_x, bezout_y) = ext_gcd(a, b);
+    let empty = NonNegativeSolutions {
+        next: (0, 0),
+        step: (0, 0),
+        remaining: 0,
+    };
+    if c < 0 || c % divisor != 0 {
+        return empty;
+    }
+    // every solution is (x0 + k * step_x, y0 - k * step_y)
+    let (x0, y0) = (bezout_x * (c / divisor), bezout_y * (c / divisor));
+    let (step_x, step_y) = (b / divisor, a / divisor);
+    let k_min = (-x0).div_euclid(step_x) + i128::from((-x0).rem_euclid(step_x) != 0);
+    let k_max = y0.div_euclid(step_y);
+    if k_min > k_max {
+        return empty;
+    }
+    NonNegativeSolutions {
+        next: (x0 + k_min * step_x, y0 - k_min * step_y),
+        step: (step_x, step_y),
+        remaining: k_max - k_min + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratios(values: &[(i128, i128)]) -> Vec<Ratio<i128>> {
+        values.iter().map(|&(n, d)| Ratio::new(n, d)).collect()
+    }
+
+    #[test]
+    fn test_ratio() {
+        let half = Ratio::new(2_i64, 4);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Ratio::new(3, -6), -half);
+        assert_eq!(half + Ratio::new(1, 3), Ratio::new(5, 6));
+        assert_eq!(half * Ratio::from_integer(4), Ratio::from_integer(2));
+        assert_eq!((half / Ratio::new(1, 4)).to_integer(), Some(2));
+        assert!(Ratio::new(-1, 2) < Ratio::new(1, 3));
+        assert_eq!(Ratio::new(-3, 6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        // 94a + 22b = 8400, 34a + 67b = 5400
+        let coefficients = Matrix::new([94, 22, 34, 67], 2, 2);
+        assert_eq!(
+            solve(&coefficients, &[8400, 5400]),
+            SystemSolution::Unique(ratios(&[(80, 1), (40, 1)]))
+        );
+        let coefficients = Matrix::new([2, 1, 1, 3], 2, 2);
+        assert_eq!(
+            solve(&coefficients, &[1, 1]),
+            SystemSolution::Unique(ratios(&[(2, 5), (1, 5)]))
+        );
+    }
+
+    #[test]
+    fn test_degenerate_systems() {
+        let coefficients = Matrix::new([1, 2, 2, 4], 2, 2);
+        assert_eq!(solve(&coefficients, &[3, 7]), SystemSolution::None);
+        assert_eq!(
+            solve(&coefficients, &[3, 6]),
+            SystemSolution::Infinite {
+                particular: ratios(&[(3, 1), (0, 1)]),
+                directions: vec![ratios(&[(-2, 1), (1, 1)])],
+            }
+        );
+        // more equations than unknowns
+        let coefficients = Matrix::new([1, 0, 0, 1, 1, 1], 2, 3);
+        assert_eq!(
+            solve(&coefficients, &[1, 2, 3]),
+            SystemSolution::Unique(ratios(&[(1, 1), (2, 1)]))
+        );
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (12, -18)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(2, 3, 3, 5), Some((8, 15)));
+        assert_eq!(crt(30, 101, 50, 103), Some((9423, 10403)));
+        assert_eq!(crt(1, 4, 3, 6), Some((9, 12)));
+        assert_eq!(crt(1, 4, 2, 6), None);
+    }
+
+    #[test]
+    fn test_non_negative_solutions() {
+        let solutions: Vec<_> = non_negative_solutions(3, 5, 22).collect();
+        assert_eq!(solutions, [(4, 2)]);
+        let solutions: Vec<_> = non_negative_solutions(2, 3, 12).collect();
+        assert_eq!(solutions, [(0, 4), (3, 2), (6, 0)]);
+        assert_eq!(non_negative_solutions(2, 3, 12).next_back(), Some((6, 0)));
+        assert_eq!(non_negative_solutions(4, 6, 7).count(), 0);
+        assert_eq!(non_negative_solutions(5, 7, 3).count(), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::Path};
+
+/// Known-correct answers, read from a manifest such as `answers/2024.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = 1234
+/// part2 = "co,de,ka,ta"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(HashMap<(u32, u32), String>);
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    /// A table or key is neither `dayN` nor `part1`/`part2`
+    InvalidKey(String),
+    /// An answer is neither a string nor an integer
+    InvalidValue(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "cannot read answers: {err}"),
+            Self::Toml(err) => write!(f, "invalid answers manifest: {err}"),
+            Self::InvalidKey(key) => write!(f, "unexpected key {key:?} in answers manifest"),
+            Self::InvalidValue(key) => {
+                write!(f, "answer {key:?} should be a string or an integer")
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl From<io::Error> for AnswersError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for AnswersError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
+
+impl Answers {
+    /// # Errors
+    /// if the manifest is not valid TOML or does not have the expected shape
+    pub fn parse(manifest: &str) -> Result<Self, AnswersError> {
+        let table: toml::Table = manifest.parse()?;
+        let mut answers = HashMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| AnswersError::InvalidKey(day_key.clone()))?;
+            let toml::Value::Table(parts) = parts else {
+                return Err(AnswersError::InvalidKey(day_key));
+            };
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    _ => return Err(AnswersError::InvalidKey(format!("{day_key}.{part_key}"))),
+                };
+                let answer = match answer {
+                    toml::Value::String(s) => s,
+                    toml::Value::Integer(n) => n.to_string(),
+                    _ => return Err(AnswersError::InvalidValue(format!("{day_key}.{part_key}"))),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Self(answers))
+    }
+
+    /// # Errors
+    /// if the file cannot be read, see also [`Answers::parse`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AnswersError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    #[must_use]
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// The outcome of checking one part against its recorded answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// No answer was recorded, `actual` is what the solver found
+    MissingAnswer {
+        actual: String,
+    },
+    MissingInput,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn check(expected: Option<&str>, actual: String) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected: expected.to_string(),
+                actual,
+            },
+            None => Self::MissingAnswer { actual },
+        }
+    }
+}
+
+/// A line by line comparison, expected lines prefixed with `-` and actual ones with `+`
+#[must_use]
+pub fn diff(expected: &str, actual: &str) -> String {
+    let (expected, actual): (Vec<_>, Vec<_>) =
+        (expected.lines().collect(), actual.lines().collect());
+    let mut lines = Vec::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => lines.push(format!("  {e}\n")),
+            (e, a) => {
+                lines.extend(e.map(|e| format!("- {e}\n")));
+                lines.extend(a.map(|a| format!("+ {a}\n")));
+            }
+        }
+    }
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let answers =
+            Answers::parse("[day1]\npart1 = 11\npart2 = \"31\"\n\n[day23]\npart2 = \"co,de\"\n")
+                .unwrap();
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(23, 2), Some("co,de"));
+        assert_eq!(answers.get(23, 1), None);
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        assert!(matches!(
+            Answers::parse("[dayone]\npart1 = 1"),
+            Err(AnswersError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Answers::parse("[day1]\npart3 = 1"),
+            Err(AnswersError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            Answers::parse("[day1]\npart1 = 1.5"),
+            Err(AnswersError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn test_verdict_and_diff() {
+        assert_eq!(Verdict::check(Some("42"), "42".to_string()), Verdict::Pass);
+        assert_eq!(
+            Verdict::check(None, "42".to_string()),
+            Verdict::MissingAnswer {
+                actual: "42".to_string()
+            }
+        );
+        assert_eq!(diff("a\nb\nc", "a\nx\nc\nd"), "  a\n- b\n+ x\n  c\n+ d\n");
+    }
+}